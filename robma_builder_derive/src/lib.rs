@@ -0,0 +1,4979 @@
+//! The macros of [robma_builder](https://docs.rs/robma_builder), use them through the re-exports of that crate.
+//!
+//! The generated code refers to the traits and types of `robma_builder_runtime` as `::robma_builder::...`.
+
+extern crate proc_macro;
+extern crate quote;
+extern crate syn;
+
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+
+/// Derives a `builder()` method that can be used to construct an instance of a `struct`.
+///
+/// The library is implemented using a procedural macro.
+/// The builder is a fluent-design pattern that simplifies the construction of a struct instance.
+/// In particular, we support the following features
+/// - No uninitialized members: checks the presence of all variables and panics in case of missing variables.
+/// - Members of type `Option` do not have to be specified and default to `None`
+/// - Repeated arguments: Members of type `Vec` can be constructed using a sequence of individual elements.
+///   Members of type `Option<Vec>` stay `None` until the first element is added.
+///   `build()` checks the number of elements with `min` and `max` and duplicates with `unique`.
+/// - Layered configuration: builders can be combined with `merge()` and `or_else()`.
+/// - Environment variables: members can be read from the environment with `from_env()` and `apply_env()`.
+/// - Dynamic access: members can be set by name at runtime with `set_by_name()` and `from_pairs()`.
+/// - Command line parsing: builders can be created from command line arguments with `parse_args()`.
+/// - Reflection: `FIELDS` describes all members, `missing_fields()` lists the required members that are still unset.
+/// - Getters and clearing: members can be read with `get_<member>()` and unset with `clear_<member>()` or `reset()`.
+/// - Renaming: the builder type and the `builder()` and `build()` functions can be renamed or skipped.
+/// - Documentation: the doc comments and `#[deprecated]` attributes of the members are copied onto their setters.
+/// - Conditional members: members disabled by `#[cfg(...)]` are left out of the builder,
+///   builder attributes inside of `#[cfg_attr(...)]` are applied when the condition holds.
+/// - Sub-builders: members whose type has a builder can be configured in place with `#[builder(sub_builder)]`.
+/// - Flattening: the setters of a member's builder can be generated on the outer builder with `#[builder(flatten)]`,
+///   if the struct of the member is marked with `#[builder(flattenable)]`.
+/// - Functions: the arguments of functions and constructors can be passed by name with the attribute [`macro@builder`].
+/// - Start arguments: members marked with `#[builder(start_fn)]` are passed to `builder()` instead of a setter.
+/// - Field rules: groups of alternative members, and members that require or conflict with others, are checked by `build()`.
+/// - Wrapping setters: the setters of `Box`, `Arc`, `Rc` and `Cow` members wrap their argument with `#[builder(setter(wrap))]`.
+/// - Debug output: unset members are shown as `<unset>`, members marked with `#[builder(sensitive)]` are redacted.
+/// - Computed members: members marked with `#[builder(computed = "...")]` are computed by `build()` from the other members.
+/// - Aliases and flags: setters get more names with `#[builder(alias = "...")]`,
+///   `bool` members marked with `#[builder(flag)]` are set without an argument.
+/// - Set once: members marked with `#[builder(set_once)]` can only be set once, `build()` or the setter fails otherwise.
+/// - Custom storage: members marked with `#[builder(field(ty = "...", build = "..."))]` are stored as another type
+///   and converted by `build()`.
+/// - Generic code: the traits `Buildable` and `Builder` are implemented for the struct and its builder.
+/// - Literal syntax: [`build!`] creates a struct with its builder from struct literal syntax.
+///
+/// # Examples:
+/// ## Basic usage
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder, Debug, PartialEq)]
+/// pub struct Command {
+///     executable: String,
+///     args: Vec<String>,
+///     env: Vec<String>,
+///     current_dir: String,
+/// }
+///
+/// let command = Command::builder()
+///        .executable("cargo".into())
+///        .args(vec!["build".into(), "--release".into()])
+///        .env(vec![])
+///        .current_dir("..".into())
+///        .build()
+///        .expect("missing arguments");
+///
+/// // The result of the above is equivalent to:
+/// let command_equivalent = Command{
+///         executable: "cargo".into(),
+///         args: vec!["build".into(), "--release".into()],
+///         env: vec![],
+///         current_dir: "..".into(),
+/// };
+/// assert_eq!(command, command_equivalent);
+/// ```
+///
+/// ## Optional members
+/// Optional members can be ommitted in the `builder` and initialized with `None`
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// pub struct Command {
+///     current_dir: Option<String>,
+/// }
+///
+/// let command = Command::builder()
+///     .build()
+///     .expect("missing arguments");
+///
+/// // Unspecified option members will be initialized with None:
+/// assert!(command.current_dir.is_none());
+/// ```
+///
+/// ## Repeated arguments
+/// Members of type `Vec` can be initialized by a sequence of elements.
+/// Use the attribute `#[builder(each = "...")]` to declare the name of the function.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// pub struct Command {
+///     #[builder(each = "arg")]
+///     args: Vec<String>,
+/// }
+///
+/// let command = Command::builder()
+///     .arg("build".to_owned())
+///     .arg("--release".to_owned())
+///     .build()
+///     .expect("missing arguments");
+///
+/// // args contains the elements of each call to `arg()`
+/// assert_eq!(command.args, vec!["build", "--release"]);
+/// ```
+///
+/// Members of type `Option<Vec>` are `None` until the first element is added,
+/// which tells apart members that were not configured from empty ones.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// pub struct Command {
+///     #[builder(each = "feature")]
+///     features: Option<Vec<String>>,
+/// }
+///
+/// let command = Command::builder().build().expect("missing arguments");
+/// assert_eq!(command.features, None);
+///
+/// let command = Command::builder()
+///     .feature("serde".to_owned())
+///     .build()
+///     .expect("missing arguments");
+/// assert_eq!(command.features, Some(vec!["serde".to_owned()]));
+/// ```
+///
+/// `#[builder(each = "...", min = 1, max = 16)]` limits the number of elements and
+/// `#[builder(each = "...", unique)]` rejects elements that appear twice, which requires `Eq` and `Hash`.
+/// `build()` returns an error naming the member if these checks fail.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder, Debug)]
+/// pub struct Job {
+///     #[builder(each = "step", min = 1, unique)]
+///     steps: Vec<String>,
+/// }
+///
+/// let error = Job::builder().build().unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "the number of elements of `steps` must be at least 1, but is 0"
+/// );
+/// ```
+///
+/// ## Merging builders
+/// `merge(other)` overrides the members with all members that are set in `other`,
+/// `or_else(other)` only fills the members that are not set yet.
+/// Repeated members count as set once they contain an element.
+/// By default `merge()` appends repeated members, use `#[builder(merge = "replace")]` to replace them instead.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// pub struct Command {
+///     executable: String,
+///     #[builder(each = "arg")]
+///     args: Vec<String>,
+///     #[builder(each = "env", merge = "replace")]
+///     env: Vec<String>,
+/// }
+///
+/// let mut defaults = Command::builder();
+/// defaults.executable("cargo".to_owned()).arg("build".to_owned()).env("A=1".to_owned());
+///
+/// let mut cli = Command::builder();
+/// cli.arg("--release".to_owned()).env("B=2".to_owned());
+///
+/// let command = defaults.merge(cli).build().expect("missing arguments");
+/// assert_eq!(command.executable, "cargo");
+/// assert_eq!(command.args, vec!["build", "--release"]);
+/// assert_eq!(command.env, vec!["B=2"]);
+/// ```
+///
+/// ## Environment variables
+/// Use `#[builder(env = "...")]` to read a member from an environment variable,
+/// or `#[builder(env_prefix = "...")]` on the struct to read every member from the variable
+/// named by the prefix followed by the upper case name of the member.
+/// The values are parsed with `FromStr`, repeated members are split at `,` or at the
+/// separator given by `#[builder(env_separator = "...")]`.
+/// `from_env()` creates a new builder, `apply_env()` updates an existing one.
+/// Both are only generated if a member is read from the environment, or a sub-builder marked with
+/// `#[builder(sub_builder, env)]` reads the environment variables of its inner struct.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// #[builder(env_prefix = "APP_")]
+/// pub struct Server {
+///     host: String,
+///     #[builder(env = "PORT")]
+///     port: u16,
+///     #[builder(each = "feature")]
+///     features: Vec<String>,
+/// }
+///
+/// std::env::set_var("APP_HOST", "localhost");
+/// std::env::set_var("PORT", "8080");
+/// std::env::set_var("APP_FEATURES", "tls,gzip");
+///
+/// let server = ServerBuilder::from_env().expect("invalid environment").build().expect("missing arguments");
+/// assert_eq!(server.host, "localhost");
+/// assert_eq!(server.port, 8080);
+/// assert_eq!(server.features, vec!["tls", "gzip"]);
+/// ```
+///
+/// ## Setting members by name
+/// With `#[builder(set_by_name)]` on the struct, `set_by_name(field, value)` parses `value` with `FromStr` and sets the member named `field`,
+/// repeated members get the value appended.
+/// `from_pairs()` creates a new builder from an iterator of `(field, value)` pairs.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// #[builder(set_by_name)]
+/// pub struct Server {
+///     host: String,
+///     port: u16,
+///     #[builder(each = "feature")]
+///     features: Vec<String>,
+/// }
+///
+/// let server = ServerBuilder::from_pairs(vec![
+///     ("host", "localhost"),
+///     ("port", "8080"),
+///     ("features", "tls"),
+///     ("features", "gzip"),
+/// ])
+/// .expect("invalid configuration")
+/// .build()
+/// .expect("missing arguments");
+/// assert_eq!(server.port, 8080);
+/// assert_eq!(server.features, vec!["tls", "gzip"]);
+///
+/// assert!(Server::builder().set_by_name("port", "eighty").is_err());
+/// assert!(Server::builder().set_by_name("hostname", "localhost").is_err());
+/// ```
+///
+/// ## Command line arguments
+/// With `#[builder(cli)]` on the struct, `parse_args(args)` creates a builder from command line arguments.
+/// Each member is set by `--member-name <value>` or `--member-name=<value>`,
+/// repeated members are set by repeating the flag named after `each`,
+/// and `bool` members are set to `true` by the presence of their flag.
+/// `--help` returns an error that displays the help text generated from the doc comments of the members,
+/// which is also returned by `help()`.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// #[builder(cli)]
+/// pub struct Command {
+///     /// The program to run.
+///     executable: String,
+///     /// An argument of the program.
+///     #[builder(each = "arg")]
+///     args: Vec<String>,
+///     /// Print what is going on.
+///     verbose: bool,
+/// }
+///
+/// let args = vec!["--executable", "cargo", "--arg", "build", "--arg=--release", "--verbose"];
+/// let command = CommandBuilder::parse_args(args.into_iter().map(String::from))
+///     .expect("invalid arguments")
+///     .build()
+///     .expect("missing arguments");
+/// assert_eq!(command.executable, "cargo");
+/// assert_eq!(command.args, vec!["build", "--release"]);
+/// assert!(command.verbose);
+///
+/// let help = CommandBuilder::parse_args(vec!["--help".to_owned()]).unwrap_err().to_string();
+/// assert!(help.contains("--executable <EXECUTABLE>  The program to run. (required)"));
+/// ```
+///
+/// ## Field metadata
+/// The constant `FIELDS` of the builder describes every member with a `robma_builder::FieldInfo`:
+/// its name, its type as written, whether it is required, optional or repeated, its `each` name and its doc comment.
+/// `missing_fields()` returns the required members that are not set yet
+/// and `is_<member>_set()` tells whether a single member is set.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// pub struct Command {
+///     /// The program to run.
+///     executable: String,
+///     current_dir: Option<String>,
+/// }
+///
+/// assert_eq!(CommandBuilder::FIELDS[0].name, "executable");
+/// assert_eq!(CommandBuilder::FIELDS[1].ty, "Option<String>");
+/// assert_eq!(CommandBuilder::FIELDS[0].doc, "The program to run.");
+///
+/// let mut builder = Command::builder();
+/// assert_eq!(builder.missing_fields(), vec!["executable"]);
+/// builder.executable("cargo".to_owned());
+/// assert!(builder.is_executable_set());
+/// assert!(builder.missing_fields().is_empty());
+/// ```
+///
+/// ## Getters and clearing
+/// `get_<member>()` returns the value of a member if it is set, or a slice of the elements of a repeated member.
+/// `clear_<member>()` unsets a single member and `reset()` unsets all members.
+///
+/// The builder and its methods have the visibility of the struct by default.
+/// Use `#[builder(vis = "...")]` on the struct to change the visibility of the builder,
+/// or on a single member to change the visibility of its setters, getters and `clear_` method.
+/// ```
+/// mod command {
+///     use robma_builder::Builder;
+///
+///     #[derive(Builder)]
+///     #[builder(vis = "pub")]
+///     pub struct Command {
+///         pub executable: String,
+///         #[builder(each = "arg")]
+///         pub args: Vec<String>,
+///         #[builder(vis = "pub(crate)")]
+///         pub current_dir: Option<String>,
+///     }
+///
+/// }
+///
+/// let mut builder = command::Command::builder();
+/// builder.executable("cargo".to_owned()).arg("build".to_owned());
+/// assert_eq!(builder.get_executable().map(String::as_str), Some("cargo"));
+/// assert_eq!(builder.get_args(), ["build"]);
+/// assert_eq!(builder.get_current_dir(), None);
+///
+/// builder.clear_executable();
+/// assert_eq!(builder.get_executable(), None);
+/// builder.reset();
+/// assert!(builder.get_args().is_empty());
+/// ```
+///
+/// ## Renaming the builder
+/// `#[builder(name = "...")]` on the struct renames the builder type,
+/// `#[builder(builder_fn(name = "..."))]` renames the `builder()` function
+/// and `#[builder(build_fn(name = "..."))]` renames the `build()` function.
+/// `builder_fn(skip)` and `build_fn(skip)` do not generate the function at all,
+/// so that it can be written by hand using the storage of the builder.
+/// `#[builder(setter(name = "..."))]` on a member renames its setter.
+/// Members whose setters or other methods would collide with a method of the builder,
+/// e.g. a member named `build`, are reported with the attribute or member to rename.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// #[builder(name = "CommandOptions", builder_fn(name = "options"), build_fn(name = "finish"))]
+/// pub struct Command {
+///     executable: String,
+///     #[builder(setter(name = "merge_env"))]
+///     merge: Option<bool>,
+/// }
+///
+/// let _options: CommandOptions = Command::options();
+/// let command = Command::options()
+///     .executable("cargo".to_owned())
+///     .merge_env(true)
+///     .finish()
+///     .expect("missing arguments");
+/// assert_eq!(command.executable, "cargo");
+/// assert_eq!(command.merge, Some(true));
+/// ```
+///
+/// ## Documentation
+/// All generated items are documented.
+/// The doc comments of a member are copied onto its setters and getters,
+/// the documentation of the builder lists the required and optional members.
+/// A `#[deprecated]` attribute on a member is copied onto its setters,
+/// so that setting a deprecated member produces a deprecation warning.
+/// ```
+/// #![deny(deprecated)]
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// pub struct Command {
+///     /// The working directory.
+///     current_dir: Option<String>,
+///     #[deprecated(note = "use `current_dir` instead")]
+///     cwd: Option<String>,
+/// }
+///
+/// Command::builder().current_dir("..".to_owned());
+/// ```
+///
+/// ## Sub-builders
+/// A member marked with `#[builder(sub_builder)]` stores the builder of its type,
+/// which is derived with `Builder` as well and implements `robma_builder::Buildable` and `robma_builder::Builder`.
+/// The member is configured with a closure, `.server(|s| s.port(80))`, or through `server_mut()`.
+/// `build()` builds the inner struct, a missing member of it is reported with its path, e.g. `server.host`,
+/// and other errors are wrapped in the `Nested` variant of `robma_builder::BuilderError`.
+/// `merge()` and `or_else()` are forwarded to the inner builder,
+/// and `set_by_name()` sets the members of the inner builder by their path, e.g. `server.port`.
+/// `apply_env()` is forwarded if the member is marked with `#[builder(sub_builder, env)]`.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder, Debug)]
+/// pub struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[derive(Builder, Debug)]
+/// pub struct Config {
+///     name: String,
+///     #[builder(sub_builder)]
+///     server: Server,
+/// }
+///
+/// let mut builder = Config::builder();
+/// builder.name("app".to_owned()).server(|s| s.host("localhost".to_owned()));
+/// builder.server_mut().port(80);
+/// let config = builder.build().expect("missing arguments");
+/// assert_eq!(config.server.port, 80);
+///
+/// let error = Config::builder().name("app".to_owned()).build().unwrap_err();
+/// assert_eq!(error.to_string(), "server.host: not initialized");
+/// let error = error.downcast_ref::<robma_builder::UninitializedFieldError>().unwrap();
+/// assert_eq!(error.field(), "server.host");
+/// ```
+///
+/// ## Flattened members
+/// A member marked with `#[builder(flatten)]` stores the builder of its type like a sub-builder,
+/// but the setters of the inner builder are generated directly on the outer builder.
+/// A setter of the inner builder with the same name as a setter of the outer builder is a compile error.
+/// The inner builder is available through `<member>_mut()`, its errors are returned unchanged by `build()`,
+/// and `set_by_name()` falls back to the members of the inner builder.
+/// With `#[builder(cli)]`, `parse_args()` passes the flags it does not know on to the inner builder
+/// and the help text lists the options of the inner builder after the own ones.
+/// This needs `#[builder(set_by_name)]` or `#[builder(cli)]` on the inner struct as well,
+/// otherwise the missing functions of the inner builder are reported at the type of the member.
+/// The inner struct has to be marked with `#[builder(flattenable)]`, which generates a hidden macro
+/// with the setters under the name of the struct. The macro is found through the path of the member type,
+/// so the struct may be imported with `use`, but it has to be derived in the same crate and its builder
+/// has to implement `robma_builder::Buildable`. The macro is imported with `use`, which needs edition 2018.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// #[builder(flattenable)]
+/// pub struct CommonOpts {
+///     timeout: u64,
+///     verbose: Option<bool>,
+/// }
+///
+/// #[derive(Builder)]
+/// pub struct Command {
+///     executable: String,
+///     #[builder(flatten)]
+///     common: CommonOpts,
+/// }
+///
+/// let command = Command::builder()
+///     .executable("cargo".to_owned())
+///     .timeout(30)
+///     .build()
+///     .expect("missing arguments");
+/// assert_eq!(command.common.timeout, 30);
+/// assert_eq!(command.common.verbose, None);
+/// ```
+///
+/// Structs that are not flattenable generate no macro, so they can be derived in any module also on edition 2015.
+/// ```edition2015
+/// extern crate robma_builder;
+///
+/// mod options {
+///     use robma_builder::Builder;
+///
+///     #[derive(Builder)]
+///     pub struct CommonOpts {
+///         pub timeout: u64,
+///     }
+/// }
+///
+/// fn main() {
+///     let options = options::CommonOpts::builder().timeout(30).build().unwrap();
+///     assert_eq!(options.timeout, 30);
+/// }
+/// ```
+///
+/// ## Start arguments
+/// Members marked with `#[builder(start_fn)]` are the arguments of `builder()`, in the order of the members.
+/// They have no setter, so a builder cannot be created without them.
+/// `build()` moves them out of the builder like the other members, so they do not have to implement `Clone`.
+/// As a consequence a start argument is missing again after `build()`: a second `build()` fails with
+/// `UninitializedFieldError` and `missing_fields()` lists it, until `merge()` takes it from another builder.
+/// `from_env()`, `from_pairs()` and `parse_args()` take them as their first arguments, `reset()` keeps them.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// pub struct Command {
+///     #[builder(start_fn)]
+///     executable: String,
+///     #[builder(each = "arg")]
+///     args: Vec<String>,
+/// }
+///
+/// let command = Command::builder("cargo".to_owned())
+///     .arg("build".to_owned())
+///     .build()
+///     .expect("missing arguments");
+/// assert_eq!(command.executable, "cargo");
+/// ```
+///
+/// ## Field rules
+/// Optional and repeated members are put into groups with `#[builder(group = "...")]`.
+/// The groups are declared on the struct with `#[builder(groups(name(mode), ...))]`,
+/// where the mode is `exactly_one`, `at_most_one` or `at_least_one`.
+/// `#[builder(requires = "...")]` and `#[builder(conflicts_with = "...")]` relate a member to another one.
+/// `build()` checks these rules before it takes any member, the error names the members that break them.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder, Debug)]
+/// #[builder(groups(stdin(exactly_one)))]
+/// pub struct Command {
+///     #[builder(group = "stdin")]
+///     stdin_file: Option<String>,
+///     #[builder(group = "stdin")]
+///     stdin_bytes: Option<Vec<u8>>,
+///     #[builder(requires = "stdin_file")]
+///     follow: Option<bool>,
+/// }
+///
+/// let error = Command::builder().stdin_bytes(vec![]).follow(true).build().unwrap_err();
+/// assert_eq!(error.to_string(), "`follow` requires `stdin_file` to be set");
+///
+/// let error = Command::builder().build().unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "one of the fields `stdin_file`, `stdin_bytes` of group `stdin` has to be set"
+/// );
+/// ```
+///
+/// ## Wrapping setters
+/// The setter of a `Box`, `Arc`, `Rc` or `Cow` member marked with `#[builder(setter(wrap))]`
+/// accepts any value that converts into the member, e.g. the plain value or a `&str`.
+/// The setter of a `Cow<T>` member accepts any value that converts into the owned type of `T` and stores `Cow::Owned`.
+/// If the pointer holds a trait object, the setter accepts any implementation of the trait, such as a closure.
+/// Builders with such members do not implement `PartialEq` and print the type of these members in `Debug`.
+/// ```
+/// use robma_builder::Builder;
+/// use std::borrow::Cow;
+/// use std::sync::Arc;
+///
+/// pub struct Event {
+///     pub code: u32,
+/// }
+///
+/// #[derive(Builder)]
+/// pub struct Server {
+///     #[builder(setter(wrap))]
+///     name: Cow<'static, str>,
+///     #[builder(setter(wrap))]
+///     root: Arc<String>,
+///     #[builder(setter(wrap))]
+///     handler: Box<dyn Fn(&Event) -> u32>,
+/// }
+///
+/// let server = Server::builder()
+///     .name("local")
+///     .root("/var/www".to_owned())
+///     .handler(|event: &Event| event.code * 2)
+///     .build()
+///     .expect("missing arguments");
+/// assert_eq!(server.name, "local");
+/// assert_eq!((server.handler)(&Event { code: 21 }), 42);
+/// ```
+///
+/// ## Debug output
+/// The builder implements `Debug`, unset members are printed as `<unset>` and optional members as `Some(...)`.
+/// Members marked with `#[builder(sensitive)]` are printed as `"<redacted>"` once they are set,
+/// their values are also left out of the errors of `set_by_name()`.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// pub struct Login {
+///     user: String,
+///     #[builder(sensitive)]
+///     password: String,
+///     domain: Option<String>,
+/// }
+///
+/// let mut builder = Login::builder();
+/// builder.user("admin".to_owned()).password("hunter2".to_owned());
+/// assert_eq!(
+///     format!("{:?}", builder),
+///     r#"LoginBuilder { user: "admin", password: "<redacted>", domain: <unset> }"#
+/// );
+/// ```
+///
+/// ## Computed members
+/// Members marked with `#[builder(computed = "...")]` have no setter.
+/// `build()` evaluates the expression once the other members are resolved, they are available as variables of the same name.
+/// `#[builder(computed(with = "..."))]` calls a function with references to the other members in the order of their declaration.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// pub struct Command {
+///     executable: String,
+///     #[builder(each = "arg")]
+///     args: Vec<String>,
+///     #[builder(computed = "format!(\"{} {}\", executable, args.join(\" \"))")]
+///     full_cmd: String,
+/// }
+///
+/// let command = Command::builder()
+///     .executable("cargo".to_owned())
+///     .arg("test".to_owned())
+///     .build()
+///     .expect("missing arguments");
+/// assert_eq!(command.full_cmd, "cargo test");
+/// ```
+///
+/// ## Aliases and flags
+/// `#[builder(alias = "...")]` adds a setter with another name that forwards to the setter of the member.
+/// A `bool` or `Option<bool>` member marked with `#[builder(flag)]` gets a setter without arguments that sets `true`,
+/// and a setter ending in `_if` that takes the value. A `bool` flag that is not set is `false`.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder)]
+/// pub struct Command {
+///     #[builder(alias = "cwd")]
+///     current_dir: String,
+///     #[builder(flag)]
+///     verbose: bool,
+///     #[builder(flag)]
+///     color: bool,
+/// }
+///
+/// let command = Command::builder()
+///     .cwd("..".to_owned())
+///     .verbose()
+///     .build()
+///     .expect("missing arguments");
+/// assert_eq!(command.current_dir, "..");
+/// assert!(command.verbose);
+/// assert!(!command.color);
+/// ```
+///
+/// ## Set once
+/// `#[builder(set_once)]` on a member or on the struct records when a member is set a second time,
+/// and `build()` returns an error that names it. It also applies to `set_by_name()`, `apply_env()` and `merge()`.
+/// With `#[builder(set_once(strict))]` the setter returns a `Result` and keeps the first value.
+/// Repeated members, sub-builders and start arguments are not affected by `set_once` on the struct.
+/// ```
+/// use robma_builder::Builder;
+///
+/// #[derive(Builder, Debug)]
+/// pub struct Command {
+///     #[builder(set_once)]
+///     executable: String,
+///     #[builder(set_once(strict))]
+///     current_dir: Option<String>,
+/// }
+///
+/// let mut builder = Command::builder();
+/// builder.current_dir("..".to_owned()).unwrap();
+/// assert!(builder.current_dir("/".to_owned()).is_err());
+///
+/// let error = builder
+///     .executable("cargo".to_owned())
+///     .executable("rustc".to_owned())
+///     .build()
+///     .unwrap_err();
+/// assert_eq!(error.to_string(), "`executable` is set more than once");
+/// ```
+///
+/// ## Custom storage
+/// `#[builder(field(ty = "...", build = "..."))]` stores a member as `ty` in the builder, starting from its default value.
+/// The setter takes a `ty`, and `build()` evaluates the `build` expression with the builder as `self`, errors are returned with `?`.
+/// The member counts as set when it differs from the default value.
+/// ```
+/// use robma_builder::Builder;
+/// use std::collections::BTreeSet;
+///
+/// #[derive(Builder)]
+/// pub struct Server {
+///     #[builder(field(ty = "String", build = "self.port.parse()?"))]
+///     port: u16,
+///     #[builder(field(ty = "Vec<String>", build = "self.tags.iter().cloned().collect()"))]
+///     tags: BTreeSet<String>,
+/// }
+///
+/// let server = Server::builder()
+///     .port("8080".to_owned())
+///     .tags(vec!["web".to_owned(), "web".to_owned()])
+///     .build()
+///     .expect("invalid port");
+/// assert_eq!(server.port, 8080);
+/// assert_eq!(server.tags.len(), 1);
+/// assert!(Server::builder().port("http".to_owned()).build().is_err());
+/// ```
+///
+/// ## Generic code
+/// The builder implements the trait `robma_builder::Builder`, unless `build_fn(skip)` is given.
+/// Builders without start arguments implement `Default`, and unless `vis` makes the builder less visible
+/// than the struct, the struct implements `robma_builder::Buildable`. A required member that is not set is reported
+/// as a `robma_builder::UninitializedFieldError`, all other errors of the builder are a `robma_builder::BuilderError`,
+/// which is also named `<Builder>Error`, e.g. `CommandBuilderError`.
+/// ```
+/// use robma_builder::{Buildable, Builder, UninitializedFieldError};
+///
+/// #[derive(Builder, Debug)]
+/// pub struct Command {
+///     executable: String,
+/// }
+///
+/// fn build_default<T: Buildable>() -> Result<T, <T::Builder as Builder>::Error>
+/// where
+///     T::Builder: Builder<Target = T>,
+/// {
+///     T::builder().build()
+/// }
+///
+/// let error = build_default::<Command>().unwrap_err();
+/// assert_eq!(
+///     error.downcast_ref::<UninitializedFieldError>().map(|error| error.field()),
+///     Some("executable")
+/// );
+/// ```
+#[proc_macro_derive(Builder, attributes(builder))]
+pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derive_input: syn::DeriveInput = syn::parse_macro_input!(input);
+
+    let mut defaults = default_struct_attributes();
+    defaults.vis = derive_input.vis.clone();
+    let struct_attributes = match get_builder_attributes(&derive_input.attrs)
+        .and_then(|nested| get_struct_attributes(nested, defaults.clone()))
+    {
+        Ok(struct_attributes) => struct_attributes,
+        Err(error) => return derive_stub(&derive_input.ident, &defaults, error).into(),
+    };
+    let fields = match get_fields(get_named_fields(&derive_input), &struct_attributes) {
+        Ok(fields) => fields,
+        Err(error) => return derive_stub(&derive_input.ident, &struct_attributes, error).into(),
+    };
+
+    derive_builder(
+        &derive_input.ident,
+        &get_docs(&derive_input.attrs),
+        &struct_attributes,
+        &fields,
+        &Target::Struct {
+            vis: &derive_input.vis,
+        },
+    )
+    .into()
+}
+
+/// Emits `error` next to an empty builder and `builder()`, so that code using the builder
+/// does not report that they are missing in addition to the actual error.
+fn derive_stub(
+    name: &syn::Ident,
+    struct_attributes: &StructAttributes,
+    error: syn::Error,
+) -> proc_macro2::TokenStream {
+    let builder_name = struct_attributes
+        .builder_name
+        .clone()
+        .unwrap_or_else(|| format_ident!("{}Builder", name));
+    let vis = &struct_attributes.vis;
+    let error = error.to_compile_error();
+    let builder_function = if struct_attributes.builder_fn.skip {
+        quote! {}
+    } else {
+        let builder_fn_name = &struct_attributes.builder_fn.name;
+        quote! {
+            impl #name {
+                #[allow(dead_code)]
+                #vis fn #builder_fn_name() -> #builder_name {
+                    #builder_name {}
+                }
+            }
+        }
+    };
+    quote! {
+        #error
+
+        #[allow(dead_code)]
+        #vis struct #builder_name {}
+
+        #builder_function
+    }
+}
+
+/// Generates a builder for the arguments of a function.
+///
+/// The function is replaced by a function with the same name that returns the builder,
+/// the finishing function `call()` of the builder calls the original function.
+/// The arguments are classified like the members of a struct with [`derive@Builder`]:
+/// arguments of type `Option` may be omitted, `#[builder(each = "...")]` appends single elements,
+/// and the arguments of `#[builder(...)]` on the function are the ones of a struct.
+/// The builder is named after the function, e.g. `ConnectBuilder` for `connect`.
+/// It stores the arguments until `call()`, so generic, borrowed and `impl Trait` arguments
+/// and arguments with `#[cfg]` are not supported, only borrows with a `'static` lifetime.
+/// ```
+/// #[robma_builder::builder]
+/// fn connect(
+///     host: String,
+///     port: Option<u16>,
+///     #[builder(each = "header")] headers: Vec<String>,
+/// ) -> String {
+///     format!("{}:{} {:?}", host, port.unwrap_or(80), headers)
+/// }
+///
+/// let connection = connect()
+///     .host("localhost".to_owned())
+///     .header("a".to_owned())
+///     .call()
+///     .expect("missing arguments");
+/// assert_eq!(connection, "localhost:80 [\"a\"]");
+/// ```
+///
+/// On an `impl` block, a builder is generated for every associated function marked with `#[builder]`.
+/// The builder of `new` is created by `builder()` and finished by `build()`,
+/// the builders of other functions keep the name of the function and are finished by `call()`.
+/// ```
+/// pub struct Connection {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[robma_builder::builder]
+/// impl Connection {
+///     #[builder]
+///     fn new(host: String, port: Option<u16>) -> Self {
+///         Connection { host, port: port.unwrap_or(80) }
+///     }
+///
+///     #[builder(build_fn(name = "open"))]
+///     fn local(port: u16) -> Self {
+///         Connection { host: "localhost".to_owned(), port }
+///     }
+/// }
+///
+/// let connection = Connection::builder()
+///     .host("example.com".to_owned())
+///     .build()
+///     .expect("missing arguments");
+/// assert_eq!(connection.port, 80);
+///
+/// let connection = Connection::local().port(8080).open().expect("missing arguments");
+/// assert_eq!(connection.host, "localhost");
+/// ```
+#[proc_macro_attribute]
+pub fn builder(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = syn::parse_macro_input!(args as syn::AttributeArgs);
+    let item: syn::Item = syn::parse_macro_input!(input);
+
+    let result = match item {
+        syn::Item::Fn(mut function) => derive_function_builder(
+            args,
+            &mut function.attrs,
+            &function.vis,
+            &mut function.sig,
+            None,
+        )
+        .map(|builder| {
+            function.vis = syn::Visibility::Inherited;
+            quote! {
+                #function
+                #builder
+            }
+        }),
+        syn::Item::Impl(item_impl) => match args.first() {
+            Some(arg) => Err(syn::Error::new_spanned(
+                arg,
+                "expected the arguments on the `#[builder(...)]` attributes of the functions",
+            )),
+            None => derive_impl_builders(item_impl),
+        },
+        item => Err(syn::Error::new_spanned(
+            item,
+            "`#[builder]` expects a function or an `impl` block",
+        )),
+    };
+
+    match result {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Creates a struct with its builder from struct literal syntax and returns the result of `build()`.
+///
+/// Every member is set with its setter, so renamed, wrapping and `set_once` setters behave as
+/// in a chain of calls. The value is converted with `Into` unless the setter wraps it.
+/// A list literal `[...]` sets a `Vec` member and appends the elements one by one, with the `each`
+/// setter of a repeated member, a member followed by `?` takes an `Option` and is only set if
+/// it is `Some`. Members that are left out keep their defaults, and `build()` checks the result
+/// as usual, the first error of a strict setter is returned instead.
+///
+/// The struct has to derive [`derive@Builder`] and implement `robma_builder::Buildable`,
+/// i.e. its builder has no start arguments and is as visible as the struct.
+/// Sub-builders, flattened members and computed members cannot be set.
+/// The macro is not named `builder!` because [`macro@builder`] already takes that name.
+/// ```
+/// use robma_builder::{build, Builder};
+///
+/// #[derive(Builder, Debug)]
+/// pub struct Command {
+///     executable: String,
+///     #[builder(each = "arg")]
+///     args: Vec<String>,
+///     current_dir: Option<String>,
+///     #[builder(flag)]
+///     verbose: bool,
+/// }
+///
+/// let current_dir: Option<&str> = None;
+/// let command = build!(Command {
+///     executable: "cargo",
+///     args: ["build", "--release"],
+///     current_dir?: current_dir,
+/// })
+/// .expect("missing arguments");
+/// assert_eq!(command.args, ["build", "--release"]);
+/// assert_eq!(command.current_dir, None);
+/// assert!(!command.verbose);
+///
+/// assert!(build!(Command { verbose: true }).is_err());
+/// ```
+#[proc_macro]
+pub fn build(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let literal: Literal = syn::parse_macro_input!(input);
+    match derive_literal(&literal) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// The input of [`macro@build`], e.g. `Command { executable: "cargo", current_dir?: dir }`.
+struct Literal {
+    path: syn::Path,
+    fields: Vec<LiteralField>,
+}
+
+struct LiteralField {
+    name: syn::Ident,
+    /// The field is followed by `?` and only set if the value is `Some`.
+    optional: bool,
+    value: syn::Expr,
+}
+
+impl syn::parse::Parse for Literal {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let content;
+        syn::braced!(content in input);
+        let mut fields = Vec::new();
+        while !content.is_empty() {
+            let name: syn::Ident = content.parse()?;
+            let optional = content.parse::<Option<syn::Token![?]>>()?.is_some();
+            // `name` alone is short for `name: name`, like in struct literals.
+            let value = if content.parse::<Option<syn::Token![:]>>()?.is_some() {
+                content.parse()?
+            } else {
+                syn::parse_quote!(#name)
+            };
+            fields.push(LiteralField {
+                name,
+                optional,
+                value,
+            });
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<syn::Token![,]>()?;
+        }
+        Ok(Literal { path, fields })
+    }
+}
+
+fn derive_literal(literal: &Literal) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let path = &literal.path;
+    let fields_type = quote! {
+        <<#path as ::robma_builder::Buildable>::Builder as ::robma_builder::__private::Literal>::Fields
+    };
+    let mut statements = Vec::new();
+    for (index, field) in literal.fields.iter().enumerate() {
+        if literal.fields[..index]
+            .iter()
+            .any(|other| other.name.unraw() == field.name.unraw())
+        {
+            return Err(syn::Error::new(
+                field.name.span(),
+                format!("field `{}` is specified more than once", field.name.unraw()),
+            ));
+        }
+
+        let name = &field.name;
+        let set = |value| {
+            quote! {
+                if __robma_builder_result.is_ok() {
+                    __robma_builder_result = #fields_type::#name(&mut __robma_builder, #value);
+                }
+            }
+        };
+        match &field.value {
+            value if field.optional => {
+                let set = set(quote! {value});
+                statements.push(quote! {
+                    if let std::option::Option::Some(value) = #value {
+                        #set
+                    }
+                });
+            }
+            // The elements are appended one by one, so that each of them is converted on its own,
+            // e.g. a closure into a `Box<dyn Fn()>`.
+            syn::Expr::Array(array) => {
+                let each_name = format_ident!("__robma_builder_each_{}", name.unraw());
+                statements.push(set(quote! {std::vec::Vec::new()}));
+                statements.extend(array.elems.iter().map(|element| {
+                    quote! {
+                        if __robma_builder_result.is_ok() {
+                            __robma_builder_result =
+                                #fields_type::#each_name(&mut __robma_builder, #element);
+                        }
+                    }
+                }));
+            }
+            value => statements.push(set(quote! {#value})),
+        }
+    }
+
+    Ok(quote! {
+        {
+            // The values are expanded in this scope, so the builder has a name that they cannot use.
+            let mut __robma_builder = <#path as ::robma_builder::Buildable>::builder();
+            #[allow(unused_mut)]
+            let mut __robma_builder_result: std::result::Result<(), ::robma_builder::BuilderError> =
+                std::result::Result::Ok(());
+            #(#statements)*
+            match __robma_builder_result {
+                std::result::Result::Ok(()) => ::robma_builder::Builder::build(&mut __robma_builder),
+                std::result::Result::Err(error) => std::result::Result::Err(std::convert::From::from(error)),
+            }
+        }
+    })
+}
+
+/// Derives the builders of all functions marked with `#[builder]` in an `impl` block.
+fn derive_impl_builders(
+    mut item_impl: syn::ItemImpl,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    if let Some((_, path, _)) = &item_impl.trait_ {
+        return Err(syn::Error::new_spanned(
+            path,
+            "`#[builder]` is not supported on trait implementations",
+        ));
+    }
+    if !item_impl.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item_impl.generics,
+            "`#[builder]` is not supported on generic `impl` blocks",
+        ));
+    }
+
+    let self_type = item_impl.self_ty.clone();
+    let mut builders = Vec::new();
+    for item in item_impl.items.iter_mut() {
+        if let syn::ImplItem::Method(method) = item {
+            let (markers, attrs) = method
+                .attrs
+                .drain(..)
+                .partition::<Vec<_>, _>(|attr| attr.path.is_ident("builder"));
+            method.attrs = attrs;
+            if markers.is_empty() {
+                continue;
+            }
+
+            let mut nested_metas = Vec::new();
+            for marker in markers {
+                match marker.parse_meta()? {
+                    syn::Meta::Path(_) => {}
+                    syn::Meta::List(list) => nested_metas.extend(list.nested),
+                    meta => {
+                        return Err(syn::Error::new_spanned(
+                            meta,
+                            "expected `builder` or `builder(...)`",
+                        ))
+                    }
+                }
+            }
+            builders.push(derive_function_builder(
+                nested_metas,
+                &mut method.attrs,
+                &method.vis,
+                &mut method.sig,
+                Some(&self_type),
+            )?);
+            method.vis = syn::Visibility::Inherited;
+        }
+    }
+
+    Ok(quote! {
+        #item_impl
+        #(#builders)*
+    })
+}
+
+/// Derives the builder of a function.
+///
+/// `attrs` and `sig` are turned into the ones of the function that is called by the builder:
+/// it is renamed, and its doc comments, `#[deprecated]` attributes and `#[builder(...)]` attributes are removed.
+fn derive_function_builder(
+    nested_metas: Vec<syn::NestedMeta>,
+    attrs: &mut Vec<syn::Attribute>,
+    vis: &syn::Visibility,
+    sig: &mut syn::Signature,
+    self_type: Option<&syn::Type>,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "`#[builder]` is not supported on async functions",
+        ));
+    }
+    if let Some(unsafety) = &sig.unsafety {
+        return Err(syn::Error::new_spanned(
+            unsafety,
+            "`#[builder]` is not supported on unsafe functions",
+        ));
+    }
+    if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "`#[builder]` is not supported on generic functions",
+        ));
+    }
+
+    let mut arguments = Vec::new();
+    for input in sig.inputs.iter_mut() {
+        let pat_type = match input {
+            syn::FnArg::Typed(pat_type) => pat_type,
+            syn::FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new_spanned(
+                    receiver,
+                    "`#[builder]` is not supported on methods with `self`",
+                ))
+            }
+        };
+        let ident = match &*pat_type.pat {
+            syn::Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {
+                pat.ident.clone()
+            }
+            pat => return Err(syn::Error::new_spanned(pat, "expected an identifier")),
+        };
+        if let Some(attr) = pat_type
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("cfg") || attr.path.is_ident("cfg_attr"))
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`#[builder]` is not supported on arguments with `#[cfg]` or `#[cfg_attr]`",
+            ));
+        }
+        check_argument_type(&pat_type.ty)?;
+        let (builder_attrs, attrs) = pat_type
+            .attrs
+            .drain(..)
+            .partition(|attr| attr.path.is_ident("builder"));
+        pat_type.attrs = attrs;
+        arguments.push(syn::Field {
+            attrs: builder_attrs,
+            vis: syn::Visibility::Inherited,
+            ident: Some(ident),
+            colon_token: Some(Default::default()),
+            ty: replace_self(&pat_type.ty, self_type)?,
+        });
+    }
+
+    let function_name = sig.ident.clone();
+    let is_new = self_type.is_some() && function_name == "new";
+    let camel_case_name = to_camel_case(&function_name.unraw().to_string());
+    let builder_name = match self_type {
+        None => format_ident!("{}Builder", camel_case_name),
+        Some(self_type) => {
+            let type_name = match self_type {
+                syn::Type::Path(t) if t.qself.is_none() => {
+                    t.path.segments.last().map(|segment| &segment.ident)
+                }
+                _ => None,
+            }
+            .ok_or_else(|| syn::Error::new_spanned(self_type, "expected the name of a type"))?;
+            if is_new {
+                format_ident!("{}Builder", type_name)
+            } else {
+                format_ident!("{}{}Builder", type_name, camel_case_name)
+            }
+        }
+    };
+    let mut defaults = default_struct_attributes();
+    defaults.builder_name = Some(builder_name);
+    defaults.vis = vis.clone();
+    if !is_new {
+        defaults.builder_fn.name = function_name.clone();
+        defaults.build_fn.name = format_ident!("call");
+    }
+    let struct_attributes = get_struct_attributes(nested_metas, defaults)?;
+    let fields = get_fields(arguments.iter(), &struct_attributes)?;
+
+    let output = match &sig.output {
+        syn::ReturnType::Default => syn::parse_quote! {()},
+        syn::ReturnType::Type(_, output) => replace_self(output, self_type)?,
+    };
+    let (function_attributes, other_attributes) = attrs.drain(..).partition::<Vec<_>, _>(|attr| {
+        attr.path.is_ident("doc") || attr.path.is_ident("deprecated")
+    });
+    *attrs = other_attributes;
+    let implementation = format_ident!("__robma_builder_{}", function_name.unraw());
+    sig.ident = implementation.clone();
+
+    Ok(derive_builder(
+        &function_name,
+        &get_docs(&function_attributes),
+        &struct_attributes,
+        &fields,
+        &Target::Function {
+            self_type,
+            implementation,
+            output: Box::new(output),
+            attributes: function_attributes.iter().collect(),
+        },
+    ))
+}
+
+/// Rejects argument types that the builder cannot store: borrows without a `'static` lifetime
+/// and `impl Trait`, which would need lifetime or type parameters on the builder.
+fn check_argument_type(t: &syn::Type) -> Result<(), syn::Error> {
+    let borrowed = |tokens: &dyn quote::ToTokens| {
+        Err(syn::Error::new_spanned(
+            tokens,
+            "`#[builder]` is not supported on arguments that borrow, use an owned type or a `'static` lifetime",
+        ))
+    };
+    let check_lifetime = |lifetime: &syn::Lifetime| {
+        if lifetime.ident == "static" {
+            Ok(())
+        } else {
+            borrowed(lifetime)
+        }
+    };
+    let check_arguments = |arguments: &syn::PathArguments| -> Result<(), syn::Error> {
+        if let syn::PathArguments::AngleBracketed(arguments) = arguments {
+            for argument in arguments.args.iter() {
+                match argument {
+                    syn::GenericArgument::Type(t) => check_argument_type(t)?,
+                    syn::GenericArgument::Lifetime(lifetime) => check_lifetime(lifetime)?,
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    };
+
+    match t {
+        syn::Type::Reference(reference) => match &reference.lifetime {
+            Some(lifetime) => {
+                check_lifetime(lifetime)?;
+                check_argument_type(&reference.elem)
+            }
+            None => borrowed(reference),
+        },
+        syn::Type::ImplTrait(impl_trait) => Err(syn::Error::new_spanned(
+            impl_trait,
+            "`#[builder]` is not supported on arguments of type `impl Trait`",
+        )),
+        syn::Type::Array(array) => check_argument_type(&array.elem),
+        syn::Type::Group(group) => check_argument_type(&group.elem),
+        syn::Type::Paren(paren) => check_argument_type(&paren.elem),
+        syn::Type::Ptr(ptr) => check_argument_type(&ptr.elem),
+        syn::Type::Slice(slice) => check_argument_type(&slice.elem),
+        syn::Type::Tuple(tuple) => tuple.elems.iter().try_for_each(check_argument_type),
+        syn::Type::Path(path) => {
+            if let Some(qself) = &path.qself {
+                check_argument_type(&qself.ty)?;
+            }
+            path.path
+                .segments
+                .iter()
+                .try_for_each(|segment| check_arguments(&segment.arguments))
+        }
+        syn::Type::TraitObject(trait_object) => {
+            trait_object
+                .bounds
+                .iter()
+                .try_for_each(|bound| match bound {
+                    syn::TypeParamBound::Trait(bound) => bound
+                        .path
+                        .segments
+                        .iter()
+                        .try_for_each(|segment| check_arguments(&segment.arguments)),
+                    syn::TypeParamBound::Lifetime(lifetime) => check_lifetime(lifetime),
+                })
+        }
+        // Function pointers and everything else may elide lifetimes.
+        _ => Ok(()),
+    }
+}
+
+/// Replaces `Self` in a type of an `impl` block by `self_type`, the builder is generated outside of the block.
+fn replace_self(t: &syn::Type, self_type: Option<&syn::Type>) -> Result<syn::Type, syn::Error> {
+    fn replace(
+        tokens: proc_macro2::TokenStream,
+        self_type: &syn::Type,
+    ) -> proc_macro2::TokenStream {
+        tokens
+            .into_iter()
+            .map(|token| match token {
+                proc_macro2::TokenTree::Ident(ident) if ident == "Self" => quote! {#self_type},
+                proc_macro2::TokenTree::Group(group) => {
+                    let mut replaced = proc_macro2::Group::new(
+                        group.delimiter(),
+                        replace(group.stream(), self_type),
+                    );
+                    replaced.set_span(group.span());
+                    proc_macro2::TokenTree::Group(replaced).into()
+                }
+                token => token.into(),
+            })
+            .collect()
+    }
+
+    match self_type {
+        Some(self_type) => syn::parse2(replace(quote! {#t}, self_type)),
+        None => Ok(t.clone()),
+    }
+}
+
+/// Converts a snake case name to camel case, e.g. `ConnectAll` for `connect_all`.
+fn to_camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// What the `build()` function of a builder creates.
+enum Target<'t> {
+    /// The struct the builder is derived for.
+    Struct {
+        /// The visibility of the struct.
+        vis: &'t syn::Visibility,
+    },
+    /// The result of a function that takes the fields as arguments, see [`macro@builder`].
+    Function {
+        /// The type of the `impl` block of the function.
+        self_type: Option<&'t syn::Type>,
+        /// The function with the original arguments and body.
+        implementation: syn::Ident,
+        /// The return type of the function.
+        output: Box<syn::Type>,
+        /// The doc comments and `#[deprecated]` attributes of the function.
+        attributes: Vec<&'t syn::Attribute>,
+    },
+}
+
+fn derive_builder(
+    name: &syn::Ident,
+    struct_docs: &[String],
+    struct_attributes: &StructAttributes,
+    fields: &[Field],
+    target: &Target,
+) -> proc_macro2::TokenStream {
+    let builder_name = struct_attributes
+        .builder_name
+        .clone()
+        .unwrap_or_else(|| format_ident!("{}Builder", name));
+    let error_name = format_ident!("{}Error", builder_name);
+
+    let vis = &struct_attributes.vis;
+
+    // Computed fields are not stored in the builder, only `build()` knows about them.
+    let all_fields = fields;
+    let fields = &all_fields
+        .iter()
+        .filter(|field| field.computed.is_none())
+        .cloned()
+        .collect::<Vec<_>>()[..];
+
+    let struct_body = fields
+        .iter()
+        .map(|x| {
+            let name = x.name;
+            let field_type = x.field_type;
+            if let Some(sub_builder) = &x.sub_builder {
+                quote! {
+                    #name: #sub_builder
+                }
+            } else if let Some(custom) = &x.custom {
+                let ty = &custom.ty;
+                quote! {
+                    #name: #ty
+                }
+            } else if x.start_fn {
+                let declared_type = x.declared_type;
+                quote! {
+                    #name: std::option::Option<#declared_type>
+                }
+            } else if x.repeated_name.is_none() || x.optional {
+                quote! {
+                    #name: std::option::Option<#field_type>
+                }
+            } else {
+                quote! {
+                    #name: #field_type
+                }
+            }
+        })
+        .chain(derive_overwritten(fields, |overwritten| {
+            quote! {
+                #overwritten: std::vec::Vec<&'static str>
+            }
+        }))
+        .collect::<Vec<proc_macro2::TokenStream>>();
+
+    let builder_body = fields
+        .iter()
+        .map(|x| {
+            let name = x.name;
+            let field_type = x.field_type;
+            let initial_value = if x.sub_builder.is_some() {
+                quote! {<#field_type as ::robma_builder::Buildable>::builder()}
+            } else if x.start_fn {
+                quote! {std::option::Option::Some(#name)}
+            } else if x.custom.is_some() {
+                quote! {std::default::Default::default()}
+            } else if x.repeated_name.is_some() && !x.optional {
+                quote! {vec![]}
+            } else {
+                quote! {None}
+            };
+            quote! {
+                #name: #initial_value
+            }
+        })
+        .chain(derive_overwritten(fields, |overwritten| {
+            quote! {
+                #overwritten: std::vec::Vec::new()
+            }
+        }))
+        .collect::<Vec<proc_macro2::TokenStream>>();
+
+    let new_builder = quote! {
+        #builder_name {
+            #(#builder_body),*
+        }
+    };
+
+    // The arguments of all functions that create a builder, followed by a comma.
+    let start_parameters = fields
+        .iter()
+        .filter(|field| field.start_fn)
+        .map(|field| {
+            let name = field.name;
+            let declared_type = field.declared_type;
+            quote! {#name: #declared_type,}
+        })
+        .collect::<proc_macro2::TokenStream>();
+
+    let builder_function = if struct_attributes.builder_fn.skip {
+        quote! {}
+    } else {
+        let builder_fn_name = &struct_attributes.builder_fn.name;
+        let doc = format!("Creates a [`{}`] with all fields unset.", builder_name);
+        match target {
+            Target::Struct { .. } => quote! {
+                impl #name {
+                    #[doc = #doc]
+                    #vis fn #builder_fn_name(#start_parameters) -> #builder_name {
+                        #new_builder
+                    }
+                }
+            },
+            Target::Function {
+                self_type,
+                attributes,
+                ..
+            } => {
+                let docs = if attributes.iter().any(|attr| attr.path.is_ident("doc")) {
+                    quote! {}
+                } else {
+                    quote! {#[doc = #doc]}
+                };
+                let function = quote! {
+                    #docs
+                    #(#attributes)*
+                    #vis fn #builder_fn_name(#start_parameters) -> #builder_name {
+                        #new_builder
+                    }
+                };
+                match self_type {
+                    Some(self_type) => quote! {
+                        impl #self_type {
+                            #function
+                        }
+                    },
+                    None => function,
+                }
+            }
+        }
+    };
+
+    let setter_functions = derive_setter_functions(fields, &error_name);
+
+    let literal_fields = match target {
+        Target::Struct { .. } => derive_literal_fields(&builder_name, &error_name, vis, fields),
+        Target::Function { .. } => quote! {},
+    };
+
+    let build_function = if struct_attributes.build_fn.skip {
+        quote! {}
+    } else {
+        derive_build_function(
+            name,
+            &struct_attributes.build_fn.name,
+            &error_name,
+            vis,
+            &struct_attributes.groups,
+            all_fields,
+            target,
+        )
+    };
+
+    let merge_functions = derive_merge_functions(&builder_name, vis, fields);
+
+    let env_functions =
+        derive_env_functions(&new_builder, &start_parameters, &error_name, vis, fields);
+
+    let set_by_name_functions = if struct_attributes.set_by_name || struct_attributes.cli {
+        derive_set_by_name_functions(&new_builder, &start_parameters, &error_name, vis, fields)
+    } else {
+        quote! {}
+    };
+
+    let cli_functions = if struct_attributes.cli {
+        derive_cli_functions(
+            &new_builder,
+            &start_parameters,
+            &error_name,
+            vis,
+            struct_docs,
+            fields,
+        )
+    } else {
+        quote! {}
+    };
+
+    let error_type = derive_error_type(&error_name, vis);
+
+    let field_info_functions = derive_field_info_functions(vis, fields);
+
+    let getter_functions = derive_getter_functions(fields);
+
+    let reset_function = derive_reset_function(vis, fields);
+
+    let builder_docs = derive_builder_docs(name, fields, target);
+
+    let flatten_macro = match target {
+        Target::Struct { .. } if struct_attributes.flattenable => {
+            derive_flatten_macro(name, &builder_name, &error_name, fields)
+        }
+        Target::Struct { .. } => quote! {},
+        Target::Function { .. } => quote! {},
+    };
+    let flatten_invocations = derive_flatten_invocations(&builder_name, fields);
+
+    let (builder_derives, debug_impl) = derive_debug_impl(&builder_name, fields);
+
+    let trait_impls = derive_trait_impls(
+        name,
+        &builder_name,
+        &new_builder,
+        struct_attributes,
+        fields,
+        target,
+    );
+
+    quote! {
+        #builder_docs
+        #builder_derives
+        #vis struct #builder_name{
+            #(#struct_body),*
+        }
+
+        #builder_function
+
+        #debug_impl
+
+        #trait_impls
+
+        impl #builder_name {
+            #setter_functions
+
+            #getter_functions
+
+            #reset_function
+
+            #build_function
+
+            #merge_functions
+
+            #env_functions
+
+            #set_by_name_functions
+
+            #cli_functions
+
+            #field_info_functions
+        }
+
+        #error_type
+
+        #literal_fields
+
+        #flatten_macro
+
+        #flatten_invocations
+    }
+}
+
+/// Implements `Default` for builders without start arguments, and the traits `Buildable` and
+/// `Builder` of the runtime crate for structs.
+///
+/// `Buildable` exposes the builder type, so it is not implemented if `vis` makes the builder less
+/// visible than the struct.
+fn derive_trait_impls(
+    name: &syn::Ident,
+    builder_name: &syn::Ident,
+    new_builder: &proc_macro2::TokenStream,
+    struct_attributes: &StructAttributes,
+    fields: &[Field],
+    target: &Target,
+) -> proc_macro2::TokenStream {
+    let has_start_fn = fields.iter().any(|field| field.start_fn);
+    let default_impl = if has_start_fn {
+        quote! {}
+    } else {
+        quote! {
+            impl std::default::Default for #builder_name {
+                fn default() -> Self {
+                    #new_builder
+                }
+            }
+        }
+    };
+
+    let struct_vis = match target {
+        Target::Struct { vis } => vis,
+        Target::Function { .. } => return default_impl,
+    };
+    let builder_vis = &struct_attributes.vis;
+    let is_visible = match (struct_vis, builder_vis) {
+        (syn::Visibility::Inherited, _) | (_, syn::Visibility::Public(_)) => true,
+        _ => quote!(#struct_vis).to_string() == quote!(#builder_vis).to_string(),
+    };
+    let buildable_impl = if has_start_fn || !is_visible {
+        quote! {}
+    } else {
+        quote! {
+            impl ::robma_builder::Buildable for #name {
+                type Builder = #builder_name;
+
+                fn builder() -> #builder_name {
+                    std::default::Default::default()
+                }
+            }
+        }
+    };
+
+    let builder_impl = if struct_attributes.build_fn.skip {
+        quote! {}
+    } else {
+        let build_fn_name = &struct_attributes.build_fn.name;
+        quote! {
+            impl ::robma_builder::Builder for #builder_name {
+                type Target = #name;
+                type Error = std::boxed::Box<dyn std::error::Error>;
+
+                fn build(&mut self) -> std::result::Result<#name, Self::Error> {
+                    self.#build_fn_name()
+                }
+            }
+        }
+    };
+
+    quote! {
+        #default_impl
+        #buildable_impl
+        #builder_impl
+    }
+}
+
+#[derive(Clone)]
+struct Field<'f> {
+    name: &'f syn::Ident,
+    declared_type: &'f syn::Type,
+    field_type: &'f syn::Type,
+    optional: bool,
+    repeated_name: Option<syn::Ident>,
+    sub_builder: Option<syn::Type>,
+    flatten: bool,
+    start_fn: bool,
+    /// The name of the setter, the name of the field unless `setter(name = "...")` is given.
+    setter_name: syn::Ident,
+    wrap: bool,
+    sensitive: bool,
+    aliases: Vec<syn::Ident>,
+    flag: bool,
+    set_once: Option<SetOnce>,
+    computed: Option<Computed>,
+    custom: Option<CustomField>,
+    group: Option<syn::LitStr>,
+    requires: Vec<syn::LitStr>,
+    conflicts_with: Vec<syn::LitStr>,
+    merge: MergeStrategy,
+    min: Option<usize>,
+    max: Option<usize>,
+    unique: bool,
+    env_variable: Option<String>,
+    /// `env` on a sub-builder, `apply_env()` is forwarded to the inner builder.
+    inner_env: bool,
+    env_separator: String,
+    docs: Vec<String>,
+    deprecated: Vec<&'f syn::Attribute>,
+    vis: syn::Visibility,
+}
+
+/// How `build()` computes a field marked with `computed`.
+#[derive(Clone)]
+enum Computed {
+    /// `computed = "..."`, an expression of the other fields.
+    Expr(syn::Expr),
+    /// `computed(with = "...")`, a function taking references to the other fields.
+    With(syn::Path),
+}
+
+/// The storage of a field marked with `field(ty = "...", build = "...")`.
+#[derive(Clone)]
+struct CustomField {
+    /// The type of the member of the builder.
+    ty: syn::Type,
+    /// Converts the member to the type of the field in `build()`.
+    build: syn::Expr,
+}
+
+/// How a field with `set_once` reports that it is set a second time.
+#[derive(Clone, Copy, PartialEq)]
+enum SetOnce {
+    /// The field is recorded and `build()` fails.
+    AtBuild,
+    /// The setter fails.
+    Strict,
+}
+
+/// How `merge()` combines a repeated field with the one of the other builder.
+#[derive(Clone, Copy, PartialEq)]
+enum MergeStrategy {
+    Append,
+    Replace,
+}
+
+/// The content of all `#[builder(...)]` attributes of the struct.
+#[derive(Clone)]
+struct StructAttributes {
+    builder_name: Option<syn::Ident>,
+    builder_fn: FunctionAttributes,
+    build_fn: FunctionAttributes,
+    env_prefix: Option<String>,
+    set_by_name: bool,
+    cli: bool,
+    /// Other builders may flatten this struct.
+    flattenable: bool,
+    vis: syn::Visibility,
+    groups: Vec<Group>,
+    set_once: Option<SetOnce>,
+}
+
+/// A group of fields declared with `groups(name(mode))`.
+#[derive(Clone)]
+struct Group {
+    name: syn::Ident,
+    /// At least one field has to be set.
+    required: bool,
+    /// At most one field may be set.
+    exclusive: bool,
+}
+
+/// The content of `builder_fn(...)` and `build_fn(...)`.
+#[derive(Clone)]
+struct FunctionAttributes {
+    name: syn::Ident,
+    skip: bool,
+}
+
+/// The content of all `#[builder(...)]` attributes of a single field.
+struct FieldAttributes {
+    repeated_name: Option<syn::Ident>,
+    /// `sub_builder` or `flatten`, both store the builder of the field.
+    sub_builder: Option<(&'static str, proc_macro2::Span)>,
+    start_fn: Option<proc_macro2::Span>,
+    setter_name: Option<syn::Ident>,
+    wrap: Option<proc_macro2::Span>,
+    sensitive: bool,
+    aliases: Vec<syn::Ident>,
+    flag: Option<proc_macro2::Span>,
+    set_once: Option<(SetOnce, proc_macro2::Span)>,
+    computed: Option<(Computed, proc_macro2::Span)>,
+    custom: Option<(CustomField, proc_macro2::Span)>,
+    group: Option<syn::LitStr>,
+    requires: Vec<syn::LitStr>,
+    conflicts_with: Vec<syn::LitStr>,
+    merge: Option<(MergeStrategy, proc_macro2::Span)>,
+    min: Option<syn::LitInt>,
+    max: Option<syn::LitInt>,
+    unique: Option<proc_macro2::Span>,
+    env: Option<String>,
+    inner_env: Option<proc_macro2::Span>,
+    env_separator: Option<syn::LitStr>,
+    vis: Option<syn::Visibility>,
+}
+
+fn get_named_fields(derive_input: &syn::DeriveInput) -> impl Iterator<Item = &syn::Field> {
+    if let syn::Data::Struct(data_struct) = &derive_input.data {
+        if let syn::Fields::Named(fields) = &data_struct.fields {
+            fields.named.iter()
+        } else {
+            unimplemented!()
+        }
+    } else {
+        unimplemented!()
+    }
+}
+
+fn get_fields<'f>(
+    fields: impl Iterator<Item = &'f syn::Field>,
+    struct_attributes: &StructAttributes,
+) -> Result<Vec<Field<'f>>, syn::Error> {
+    let mut errors: Option<syn::Error> = None;
+    let mut result = Vec::new();
+    for x in fields {
+        match get_field(x, struct_attributes) {
+            Ok(field) => result.push(field),
+            Err(error) => match errors.as_mut() {
+                Some(errors) => errors.combine(error),
+                None => errors = Some(error),
+            },
+        }
+    }
+
+    // `requires` and `conflicts_with` refer to other fields, groups to at least one field.
+    let mut reference_errors = Vec::new();
+    for field in result.iter() {
+        for other in field.requires.iter().chain(&field.conflicts_with) {
+            if !result
+                .iter()
+                .any(|x| x.computed.is_none() && x.name.unraw() == other.value())
+            {
+                reference_errors.push(syn::Error::new(other.span(), "unknown field"));
+            }
+        }
+    }
+    reference_errors.extend(check_method_names(&result, struct_attributes));
+    for group in struct_attributes.groups.iter() {
+        let is_member = |field: &Field| field.group.iter().any(|name| group.name == name.value());
+        if !result.iter().any(is_member) {
+            reference_errors.push(syn::Error::new_spanned(
+                &group.name,
+                "group without fields, add `#[builder(group = \"...\")]` to its fields",
+            ));
+        }
+    }
+    for error in reference_errors {
+        match errors.as_mut() {
+            Some(errors) => errors.combine(error),
+            None => errors = Some(error),
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(result),
+    }
+}
+
+/// A method of the builder generated for a field.
+struct MethodName {
+    name: syn::Ident,
+    /// Describes the method in errors, e.g. "the setter `arg()` of field `args`".
+    description: String,
+    /// How to resolve a collision with this method.
+    rename: &'static str,
+}
+
+/// Returns the names of the methods generated for a field, including its aliases.
+fn get_method_names(field: &Field) -> Vec<MethodName> {
+    let field_name = field.name.unraw();
+    let derived = |name: String, kind: &str| MethodName {
+        name: syn::Ident::new(&name, field.name.span()),
+        description: format!("the {} `{}()` of field `{}`", kind, name, field_name),
+        rename: "rename the field",
+    };
+
+    let setter_name = field.setter_name.unraw();
+    let setter = |name: String| MethodName {
+        name: syn::Ident::new(&name, field.setter_name.span()),
+        description: format!("the setter `{}()` of field `{}`", name, field_name),
+        rename: "rename the setter with `#[builder(setter(name = \"...\"))]`",
+    };
+
+    let mut names = Vec::new();
+    if field.computed.is_some() {
+        return names;
+    }
+    if let Some(repeated_name) = &field.repeated_name {
+        names.push(MethodName {
+            name: repeated_name.clone(),
+            description: format!("the setter `{}()` of field `{}`", repeated_name, field_name),
+            rename: "choose another `each` name",
+        });
+    } else if field.flatten {
+        names.push(derived(format!("{}_mut", field_name), "method"));
+    } else if field.sub_builder.is_some() {
+        names.push(setter(setter_name.to_string()));
+        names.push(derived(format!("{}_mut", field_name), "method"));
+    } else if !field.start_fn {
+        names.push(setter(setter_name.to_string()));
+        if field.flag {
+            names.push(setter(format!("{}_if", setter_name)));
+        }
+    }
+    names.extend(field.aliases.iter().map(|alias| MethodName {
+        name: alias.clone(),
+        description: format!("the alias `{}()` of field `{}`", alias, field_name),
+        rename: "choose another alias",
+    }));
+    names.push(derived(format!("get_{}", field_name), "getter"));
+    if !field.start_fn {
+        names.push(derived(format!("clear_{}", field_name), "method"));
+        names.push(derived(format!("is_{}_set", field_name), "method"));
+    }
+    names
+}
+
+/// Checks that the methods generated for the fields do not collide with each other or with the
+/// other methods of the builder.
+fn check_method_names(fields: &[Field], struct_attributes: &StructAttributes) -> Vec<syn::Error> {
+    let mut builder_methods = vec!["reset", "merge", "or_else", "missing_fields"];
+    if fields.iter().any(has_env) {
+        builder_methods.extend(&["from_env", "apply_env"]);
+    }
+    if struct_attributes.set_by_name || struct_attributes.cli {
+        builder_methods.extend(&["set_by_name", "from_pairs"]);
+    }
+    if struct_attributes.cli {
+        builder_methods.extend(&["help", "parse_args"]);
+    }
+    let build_fn = &struct_attributes.build_fn;
+    let build_fn_name = build_fn.name.unraw().to_string();
+    if !build_fn.skip {
+        builder_methods.push(&build_fn_name);
+    }
+
+    let mut names: Vec<MethodName> = builder_methods
+        .into_iter()
+        .map(|name| MethodName {
+            name: syn::Ident::new(name, proc_macro2::Span::call_site()),
+            description: format!("the method `{}()` of the builder", name),
+            rename: "",
+        })
+        .collect();
+    let mut errors = Vec::new();
+    for method in fields.iter().flat_map(get_method_names) {
+        let name = method.name.unraw();
+        if let Some(other) = names.iter().find(|other| other.name.unraw() == name) {
+            let hint = if other.name == build_fn_name && !build_fn.skip {
+                " or the method with `build_fn(name = \"...\")`"
+            } else {
+                ""
+            };
+            errors.push(syn::Error::new(
+                method.name.span(),
+                format!(
+                    "{} collides with {}, {}{}",
+                    method.description, other.description, method.rename, hint
+                ),
+            ));
+        } else {
+            names.push(method);
+        }
+    }
+    errors
+}
+
+fn get_field<'f>(
+    field: &'f syn::Field,
+    struct_attributes: &StructAttributes,
+) -> Result<Field<'f>, syn::Error> {
+    let name = field.ident.as_ref().expect("Expected identifier");
+    let attributes = get_field_attributes(&field.attrs)?;
+    let (field_type, optional) = if is_option(&field.ty) {
+        (
+            get_angle_bracket_arg(&field.ty).expect("Expected Option Type"),
+            true,
+        )
+    } else {
+        (&field.ty, false)
+    };
+
+    let merge = match attributes.merge {
+        Some((_, span)) if attributes.repeated_name.is_none() => {
+            return Err(syn::Error::new(
+                span,
+                "`merge` is only supported together with `each = \"...\"`",
+            ));
+        }
+        Some((merge, _)) => merge,
+        None => MergeStrategy::Append,
+    };
+
+    let min = attributes
+        .min
+        .as_ref()
+        .map(syn::LitInt::base10_parse::<usize>)
+        .transpose()?;
+    let max = attributes
+        .max
+        .as_ref()
+        .map(syn::LitInt::base10_parse::<usize>)
+        .transpose()?;
+    let count_span = attributes
+        .min
+        .as_ref()
+        .or(attributes.max.as_ref())
+        .map(syn::LitInt::span)
+        .or(attributes.unique);
+    match count_span {
+        Some(span) if attributes.repeated_name.is_none() => {
+            return Err(syn::Error::new(
+                span,
+                "`min`, `max` and `unique` are only supported together with `each = \"...\"`",
+            ));
+        }
+        _ => {}
+    }
+    if let (Some(min), Some(max), Some(max_literal)) = (min, max, &attributes.max) {
+        if max < min {
+            return Err(syn::Error::new_spanned(
+                max_literal,
+                "`max` must not be less than `min`",
+            ));
+        }
+    }
+
+    let sub_builder = match attributes.sub_builder {
+        Some((kind, span)) if optional || attributes.repeated_name.is_some() => {
+            return Err(syn::Error::new(
+                span,
+                format!("`{}` is not supported on `Option` or repeated fields", kind),
+            ));
+        }
+        Some((kind, span)) if attributes.env.is_some() => {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "`{0}` fields are read from the environment variables of the inner struct, use `#[builder({0}, env)]`",
+                    kind
+                ),
+            ));
+        }
+        Some((kind, span)) => Some(get_sub_builder_type(field_type).ok_or_else(|| {
+            syn::Error::new(span, format!("`{}` expects a field of a struct type", kind))
+        })?),
+        None => None,
+    };
+    if let (Some(span), None) = (attributes.inner_env, &sub_builder) {
+        return Err(syn::Error::new(
+            span,
+            "`env` without a variable is only supported on `sub_builder` and `flatten` fields, use `env = \"...\"`",
+        ));
+    }
+
+    if let Some(span) = attributes.start_fn {
+        if attributes.repeated_name.is_some()
+            || attributes.sub_builder.is_some()
+            || attributes.env.is_some()
+        {
+            return Err(syn::Error::new(
+                span,
+                "`start_fn` cannot be combined with `each`, `sub_builder`, `flatten` or `env`",
+            ));
+        }
+    }
+
+    if let Some(span) = attributes.wrap {
+        let wrapped_type = if attributes.repeated_name.is_some() {
+            get_angle_bracket_arg(field_type)
+        } else {
+            Some(field_type)
+        };
+        if attributes.sub_builder.is_some()
+            || attributes.start_fn.is_some()
+            || wrapped_type.and_then(get_wrapper_name).is_none()
+        {
+            return Err(syn::Error::new(
+                span,
+                "`setter(wrap)` expects a setter of `Box`, `Arc`, `Rc` or `Cow`",
+            ));
+        }
+    }
+
+    if let Some((_, span)) = &attributes.computed {
+        if attributes.repeated_name.is_some()
+            || attributes.sub_builder.is_some()
+            || attributes.start_fn.is_some()
+            || attributes.setter_name.is_some()
+            || attributes.wrap.is_some()
+            || attributes.sensitive
+            || !attributes.aliases.is_empty()
+            || attributes.flag.is_some()
+            || attributes.set_once.is_some()
+            || attributes.custom.is_some()
+            || attributes.group.is_some()
+            || !attributes.requires.is_empty()
+            || !attributes.conflicts_with.is_empty()
+            || attributes.merge.is_some()
+            || attributes.min.is_some()
+            || attributes.max.is_some()
+            || attributes.unique.is_some()
+            || attributes.env.is_some()
+            || attributes.env_separator.is_some()
+            || attributes.vis.is_some()
+        {
+            return Err(syn::Error::new(
+                *span,
+                "`computed` fields have no setter and cannot be combined with other attributes",
+            ));
+        }
+    }
+
+    if let Some(span) = attributes.flag {
+        if !is_bool(field_type) || attributes.repeated_name.is_some() || attributes.wrap.is_some() {
+            return Err(syn::Error::new(
+                span,
+                "`flag` expects a field of type `bool` or `Option<bool>`",
+            ));
+        }
+    }
+    if let Some((_, span)) = &attributes.custom {
+        if attributes.repeated_name.is_some()
+            || attributes.sub_builder.is_some()
+            || attributes.start_fn.is_some()
+            || attributes.wrap.is_some()
+            || attributes.flag.is_some()
+            || attributes.set_once.is_some()
+        {
+            return Err(syn::Error::new(
+                *span,
+                "`field(...)` cannot be combined with `each`, `sub_builder`, `flatten`, `start_fn`, `setter(wrap)`, `flag` or `set_once`",
+            ));
+        }
+    }
+    if let Some(setter_name) = &attributes.setter_name {
+        if attributes.repeated_name.is_some()
+            || matches!(attributes.sub_builder, Some(("flatten", _)))
+            || attributes.start_fn.is_some()
+        {
+            return Err(syn::Error::new(
+                setter_name.span(),
+                "`setter(name = \"...\")` is not supported together with `each`, `flatten` or `start_fn`",
+            ));
+        }
+    }
+    if let Some(alias) = attributes.aliases.first() {
+        if attributes.sub_builder.is_some() || attributes.start_fn.is_some() {
+            return Err(syn::Error::new(
+                alias.span(),
+                "`alias` is not supported together with `sub_builder`, `flatten` or `start_fn`",
+            ));
+        }
+    }
+
+    // Only fields whose setter stores the value in an `Option` can be set twice.
+    let has_value_setter = attributes.repeated_name.is_none()
+        && attributes.sub_builder.is_none()
+        && attributes.start_fn.is_none()
+        && attributes.computed.is_none()
+        && attributes.custom.is_none();
+    let set_once = match attributes.set_once {
+        Some((_, span)) if !has_value_setter => {
+            return Err(syn::Error::new(
+                span,
+                "`set_once` is not supported together with `each`, `sub_builder`, `flatten`, `start_fn` or `computed`",
+            ));
+        }
+        Some((set_once, _)) => Some(set_once),
+        None if has_value_setter => struct_attributes.set_once,
+        None => None,
+    };
+
+    let rule = attributes
+        .group
+        .iter()
+        .chain(&attributes.requires)
+        .chain(&attributes.conflicts_with)
+        .next();
+    if let Some(rule) = rule {
+        if !optional && attributes.repeated_name.is_none() {
+            return Err(syn::Error::new(
+                rule.span(),
+                "`group`, `requires` and `conflicts_with` are only supported on `Option` and repeated fields",
+            ));
+        }
+    }
+    if let Some(group) = &attributes.group {
+        if !struct_attributes
+            .groups
+            .iter()
+            .any(|declared| declared.name == group.value())
+        {
+            return Err(syn::Error::new(
+                group.span(),
+                "unknown group, declare it with `#[builder(groups(name(exactly_one)))]` on the struct",
+            ));
+        }
+    }
+
+    let env_variable = match (attributes.env, &struct_attributes.env_prefix) {
+        _ if sub_builder.is_some() || attributes.start_fn.is_some() => None,
+        (Some(env), _) => Some(env),
+        (None, Some(prefix)) => Some(format!(
+            "{}{}",
+            prefix,
+            name.unraw().to_string().to_uppercase()
+        )),
+        (None, None) => None,
+    };
+
+    let env_separator = match attributes.env_separator {
+        Some(separator) if attributes.repeated_name.is_none() => {
+            return Err(syn::Error::new(
+                separator.span(),
+                "`env_separator` is only supported together with `each = \"...\"`",
+            ));
+        }
+        Some(separator) => separator.value(),
+        None => ",".to_owned(),
+    };
+
+    Ok(Field {
+        name,
+        declared_type: &field.ty,
+        field_type,
+        optional,
+        repeated_name: attributes.repeated_name,
+        sub_builder,
+        flatten: matches!(attributes.sub_builder, Some(("flatten", _))),
+        start_fn: attributes.start_fn.is_some(),
+        setter_name: attributes.setter_name.unwrap_or_else(|| name.clone()),
+        wrap: attributes.wrap.is_some(),
+        sensitive: attributes.sensitive,
+        aliases: attributes.aliases,
+        flag: attributes.flag.is_some(),
+        set_once,
+        computed: attributes.computed.map(|(computed, _)| computed),
+        custom: attributes.custom.map(|(custom, _)| custom),
+        group: attributes.group,
+        requires: attributes.requires,
+        conflicts_with: attributes.conflicts_with,
+        merge,
+        min,
+        max,
+        unique: attributes.unique.is_some(),
+        env_variable,
+        inner_env: attributes.inner_env.is_some(),
+        env_separator,
+        docs: get_docs(&field.attrs),
+        deprecated: field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("deprecated"))
+            .collect(),
+        vis: attributes
+            .vis
+            .unwrap_or_else(|| struct_attributes.vis.clone()),
+    })
+}
+
+/// Returns the `key = value` pairs of all `#[builder(...)]` attributes.
+fn get_builder_attributes(attrs: &[syn::Attribute]) -> Result<Vec<syn::NestedMeta>, syn::Error> {
+    let mut result = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("builder")) {
+        match attr.parse_meta()? {
+            syn::Meta::List(list) => result.extend(list.nested),
+            meta => return Err(syn::Error::new_spanned(meta, "expected `builder(...)`")),
+        }
+    }
+    Ok(result)
+}
+
+fn default_struct_attributes() -> StructAttributes {
+    StructAttributes {
+        builder_name: None,
+        builder_fn: FunctionAttributes {
+            name: format_ident!("builder"),
+            skip: false,
+        },
+        build_fn: FunctionAttributes {
+            name: format_ident!("build"),
+            skip: false,
+        },
+        env_prefix: None,
+        set_by_name: false,
+        cli: false,
+        flattenable: false,
+        vis: syn::Visibility::Inherited,
+        groups: Vec::new(),
+        set_once: None,
+    }
+}
+
+/// Applies the `#[builder(...)]` attributes of a struct to `attributes`.
+fn get_struct_attributes(
+    nested_metas: Vec<syn::NestedMeta>,
+    mut attributes: StructAttributes,
+) -> Result<StructAttributes, syn::Error> {
+    for nested in nested_metas {
+        match &nested {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("name") =>
+            {
+                attributes.builder_name = Some(get_ident(&name_value.lit)?);
+            }
+            syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("builder_fn") => {
+                get_function_attributes(list, &mut attributes.builder_fn)?;
+            }
+            syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("build_fn") => {
+                get_function_attributes(list, &mut attributes.build_fn)?;
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("env_prefix") =>
+            {
+                attributes.env_prefix = Some(get_string_literal(&name_value.lit)?.value());
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("set_by_name") => {
+                attributes.set_by_name = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("cli") => {
+                attributes.cli = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("flattenable") => {
+                attributes.flattenable = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("vis") =>
+            {
+                attributes.vis = get_visibility(&name_value.lit)?;
+            }
+            syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("groups") => {
+                for group in list.nested.iter() {
+                    attributes.groups.push(get_group(group)?);
+                }
+            }
+            syn::NestedMeta::Meta(meta) if meta.path().is_ident("set_once") => {
+                attributes.set_once = Some(get_set_once(meta)?);
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &nested,
+                    "unknown builder attribute",
+                ))
+            }
+        }
+    }
+
+    Ok(attributes)
+}
+
+/// Parses `set_once` or `set_once(strict)`.
+fn get_set_once(meta: &syn::Meta) -> Result<SetOnce, syn::Error> {
+    match meta {
+        syn::Meta::Path(_) => Ok(SetOnce::AtBuild),
+        syn::Meta::List(list) if list.nested.len() == 1 => match list.nested.first() {
+            Some(syn::NestedMeta::Meta(syn::Meta::Path(path))) if path.is_ident("strict") => {
+                Ok(SetOnce::Strict)
+            }
+            _ => Err(syn::Error::new_spanned(
+                meta,
+                "expected `set_once` or `set_once(strict)`",
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            meta,
+            "expected `set_once` or `set_once(strict)`",
+        )),
+    }
+}
+
+/// Parses a group like `source(exactly_one)`.
+fn get_group(nested: &syn::NestedMeta) -> Result<Group, syn::Error> {
+    let error = || {
+        syn::Error::new_spanned(
+            nested,
+            "expected `name(exactly_one)`, `name(at_most_one)` or `name(at_least_one)`",
+        )
+    };
+    let list = match nested {
+        syn::NestedMeta::Meta(syn::Meta::List(list)) if list.nested.len() == 1 => list,
+        _ => return Err(error()),
+    };
+    let (required, exclusive) = match list.nested.first() {
+        Some(syn::NestedMeta::Meta(syn::Meta::Path(path))) if path.is_ident("exactly_one") => {
+            (true, true)
+        }
+        Some(syn::NestedMeta::Meta(syn::Meta::Path(path))) if path.is_ident("at_most_one") => {
+            (false, true)
+        }
+        Some(syn::NestedMeta::Meta(syn::Meta::Path(path))) if path.is_ident("at_least_one") => {
+            (true, false)
+        }
+        _ => return Err(error()),
+    };
+    Ok(Group {
+        name: list.path.get_ident().ok_or_else(error)?.clone(),
+        required,
+        exclusive,
+    })
+}
+
+fn get_function_attributes(
+    list: &syn::MetaList,
+    attributes: &mut FunctionAttributes,
+) -> Result<(), syn::Error> {
+    for nested in list.nested.iter() {
+        match nested {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("name") =>
+            {
+                attributes.name = get_ident(&name_value.lit)?;
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("skip") => {
+                attributes.skip = true;
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    nested,
+                    "expected `name = \"...\"` or `skip`",
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn get_field_attributes(attrs: &[syn::Attribute]) -> Result<FieldAttributes, syn::Error> {
+    let mut attributes = FieldAttributes {
+        repeated_name: None,
+        sub_builder: None,
+        start_fn: None,
+        setter_name: None,
+        wrap: None,
+        sensitive: false,
+        aliases: vec![],
+        flag: None,
+        set_once: None,
+        computed: None,
+        custom: None,
+        group: None,
+        requires: Vec::new(),
+        conflicts_with: Vec::new(),
+        merge: None,
+        min: None,
+        max: None,
+        unique: None,
+        env: None,
+        inner_env: None,
+        env_separator: None,
+        vis: None,
+    };
+
+    for nested in get_builder_attributes(attrs)? {
+        match &nested {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("each") =>
+            {
+                attributes.repeated_name = Some(get_ident(&name_value.lit)?);
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                if path.is_ident("sub_builder") || path.is_ident("flatten") =>
+            {
+                if attributes.sub_builder.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        "`sub_builder` and `flatten` can only be used once per field",
+                    ));
+                }
+                let kind = if path.is_ident("flatten") {
+                    "flatten"
+                } else {
+                    "sub_builder"
+                };
+                attributes.sub_builder = Some((kind, syn::spanned::Spanned::span(path)));
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("start_fn") => {
+                attributes.start_fn = Some(syn::spanned::Spanned::span(path));
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("sensitive") => {
+                attributes.sensitive = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("alias") =>
+            {
+                attributes.aliases.push(get_ident(&name_value.lit)?);
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("flag") => {
+                attributes.flag = Some(syn::spanned::Spanned::span(path));
+            }
+            syn::NestedMeta::Meta(meta) if meta.path().is_ident("set_once") => {
+                attributes.set_once =
+                    Some((get_set_once(meta)?, syn::spanned::Spanned::span(meta)));
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("computed") =>
+            {
+                let expr = get_string_literal(&name_value.lit)?;
+                attributes.computed = Some((Computed::Expr(expr.parse()?), expr.span()));
+            }
+            syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("computed") => {
+                let function = match list.nested.iter().next() {
+                    Some(syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)))
+                        if name_value.path.is_ident("with") && list.nested.len() == 1 =>
+                    {
+                        get_string_literal(&name_value.lit)?
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            list,
+                            "expected `computed(with = \"...\")`",
+                        ))
+                    }
+                };
+                attributes.computed = Some((Computed::With(function.parse()?), function.span()));
+            }
+            syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("field") => {
+                let mut ty = None;
+                let mut build = None;
+                for nested in list.nested.iter() {
+                    match nested {
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                            if name_value.path.is_ident("ty") =>
+                        {
+                            ty = Some(get_string_literal(&name_value.lit)?.parse()?);
+                        }
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                            if name_value.path.is_ident("build") =>
+                        {
+                            build = Some(get_string_literal(&name_value.lit)?.parse()?);
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                nested,
+                                "expected `ty = \"...\"` or `build = \"...\"`",
+                            ))
+                        }
+                    }
+                }
+                let custom = match (ty, build) {
+                    (Some(ty), Some(build)) => CustomField { ty, build },
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            list,
+                            "expected `field(ty = \"...\", build = \"...\")`",
+                        ))
+                    }
+                };
+                attributes.custom = Some((custom, syn::spanned::Spanned::span(list)));
+            }
+            syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("setter") => {
+                for nested in list.nested.iter() {
+                    match nested {
+                        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("wrap") => {
+                            attributes.wrap = Some(syn::spanned::Spanned::span(path));
+                        }
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                            if name_value.path.is_ident("name") =>
+                        {
+                            attributes.setter_name = Some(get_ident(&name_value.lit)?);
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                nested,
+                                "expected `wrap` or `name = \"...\"`",
+                            ))
+                        }
+                    }
+                }
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("group") =>
+            {
+                attributes.group = Some(get_string_literal(&name_value.lit)?.clone());
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("requires") =>
+            {
+                attributes
+                    .requires
+                    .push(get_string_literal(&name_value.lit)?.clone());
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("conflicts_with") =>
+            {
+                attributes
+                    .conflicts_with
+                    .push(get_string_literal(&name_value.lit)?.clone());
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("merge") =>
+            {
+                let merge = get_string_literal(&name_value.lit)?;
+                let strategy = match merge.value().as_str() {
+                    "append" => MergeStrategy::Append,
+                    "replace" => MergeStrategy::Replace,
+                    _ => {
+                        return Err(syn::Error::new(
+                            merge.span(),
+                            "expected `merge = \"append\"` or `merge = \"replace\"`",
+                        ))
+                    }
+                };
+                attributes.merge = Some((strategy, merge.span()));
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("min") =>
+            {
+                attributes.min = Some(get_integer_literal(&name_value.lit)?.clone());
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("max") =>
+            {
+                attributes.max = Some(get_integer_literal(&name_value.lit)?.clone());
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("unique") => {
+                attributes.unique = Some(syn::spanned::Spanned::span(path));
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("env") =>
+            {
+                attributes.env = Some(get_string_literal(&name_value.lit)?.value());
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("env") => {
+                attributes.inner_env = Some(syn::spanned::Spanned::span(path));
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("env_separator") =>
+            {
+                attributes.env_separator = Some(get_string_literal(&name_value.lit)?.clone());
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                if name_value.path.is_ident("vis") =>
+            {
+                attributes.vis = Some(get_visibility(&name_value.lit)?);
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &nested,
+                    "unknown builder attribute",
+                ))
+            }
+        }
+    }
+
+    Ok(attributes)
+}
+
+/// Returns the lines of all `///` doc comments.
+fn get_docs(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(doc),
+                ..
+            })) => {
+                let doc = doc.value();
+                let doc = doc.strip_prefix(' ').unwrap_or(&doc);
+                Some(doc.trim_end().to_owned())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn get_ident(lit: &syn::Lit) -> Result<syn::Ident, syn::Error> {
+    let lit = get_string_literal(lit)?;
+    lit.parse()
+        .map_err(|_| syn::Error::new(lit.span(), "expected an identifier"))
+}
+
+fn get_visibility(lit: &syn::Lit) -> Result<syn::Visibility, syn::Error> {
+    let lit = get_string_literal(lit)?;
+    lit.parse()
+        .map_err(|_| syn::Error::new(lit.span(), "expected a visibility like `pub(crate)`"))
+}
+
+fn get_string_literal(lit: &syn::Lit) -> Result<&syn::LitStr, syn::Error> {
+    if let syn::Lit::Str(lit) = lit {
+        Ok(lit)
+    } else {
+        Err(syn::Error::new_spanned(lit, "expected a string literal"))
+    }
+}
+
+fn get_integer_literal(lit: &syn::Lit) -> Result<&syn::LitInt, syn::Error> {
+    if let syn::Lit::Int(lit) = lit {
+        Ok(lit)
+    } else {
+        Err(syn::Error::new_spanned(lit, "expected an integer literal"))
+    }
+}
+
+fn is_option(t: &syn::Type) -> bool {
+    match t {
+        syn::Type::Path(t) => match t.path.segments.first() {
+            Some(t) => t.ident == "Option",
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns the first type argument of the last segment, e.g. `str` for `std::borrow::Cow<'a, str>`.
+fn get_angle_bracket_arg(t: &syn::Type) -> Option<&syn::Type> {
+    if let syn::Type::Path(t) = t {
+        if let Some(t) = t.path.segments.last() {
+            if let syn::PathArguments::AngleBracketed(t) = &t.arguments {
+                return t.args.iter().find_map(|t| match t {
+                    syn::GenericArgument::Type(t) => Some(t),
+                    _ => None,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Returns `Box`, `Arc`, `Rc` or `Cow` if `t` is one of these types, see `setter(wrap)`.
+fn get_wrapper_name(t: &syn::Type) -> Option<String> {
+    match t {
+        syn::Type::Path(t) if t.qself.is_none() => t
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .filter(|name| ["Box", "Arc", "Rc", "Cow"].contains(&name.as_str())),
+        _ => None,
+    }
+}
+
+/// The argument of a setter for a value of type `t`.
+struct SetterArgument {
+    /// The generic parameters of the setter, including the angle brackets.
+    generics: proc_macro2::TokenStream,
+    argument_type: proc_macro2::TokenStream,
+    /// Converts the argument `x` to `t`.
+    value: proc_macro2::TokenStream,
+}
+
+/// Returns the argument of a setter of `t`, which is written as `type_tokens`.
+///
+/// With `setter(wrap)` the setter takes everything that converts into `t`, into the owned type
+/// for `Cow`, or any implementation of the trait for pointers to trait objects like `Box<dyn Fn()>`.
+fn derive_setter_argument(
+    t: &syn::Type,
+    wrap: bool,
+    type_tokens: proc_macro2::TokenStream,
+) -> SetterArgument {
+    if !wrap {
+        return SetterArgument {
+            generics: quote! {},
+            argument_type: type_tokens,
+            value: quote! {x},
+        };
+    }
+
+    let wrapper_name = get_wrapper_name(t);
+    if let (Some("Cow"), Some(borrowed)) = (wrapper_name.as_deref(), get_angle_bracket_arg(t)) {
+        return SetterArgument {
+            generics: quote! {<V: std::convert::Into<<#borrowed as std::borrow::ToOwned>::Owned>>},
+            argument_type: quote! {V},
+            value: quote! {std::borrow::Cow::Owned(std::convert::Into::into(x))},
+        };
+    }
+
+    let trait_object = match get_angle_bracket_arg(t) {
+        Some(syn::Type::TraitObject(trait_object)) => Some(trait_object),
+        _ => None,
+    };
+    let pointer = match wrapper_name.as_deref() {
+        Some("Box") => Some(quote! {std::boxed::Box}),
+        Some("Arc") => Some(quote! {std::sync::Arc}),
+        Some("Rc") => Some(quote! {std::rc::Rc}),
+        _ => None,
+    };
+    match (trait_object, pointer) {
+        (Some(trait_object), Some(pointer)) => {
+            let bounds = &trait_object.bounds;
+            let has_lifetime = bounds
+                .iter()
+                .any(|bound| matches!(bound, syn::TypeParamBound::Lifetime(_)));
+            let lifetime = if has_lifetime {
+                quote! {}
+            } else {
+                quote! {+ 'static}
+            };
+            SetterArgument {
+                generics: quote! {<F: #bounds #lifetime>},
+                argument_type: quote! {F},
+                value: quote! {#pointer::new(x)},
+            }
+        }
+        _ => SetterArgument {
+            generics: quote! {<V: std::convert::Into<#type_tokens>>},
+            argument_type: quote! {V},
+            value: quote! {std::convert::Into::into(x)},
+        },
+    }
+}
+
+/// The builder member that records the `set_once` fields that were set a second time.
+fn overwritten_field() -> syn::Ident {
+    format_ident!("__robma_builder_overwritten")
+}
+
+/// Derives the code of the member from `overwritten_field`, if any field uses `set_once`.
+fn derive_overwritten(
+    fields: &[Field],
+    derive: impl Fn(syn::Ident) -> proc_macro2::TokenStream,
+) -> Option<proc_macro2::TokenStream> {
+    if fields.iter().any(|field| field.set_once.is_some()) {
+        Some(derive(overwritten_field()))
+    } else {
+        None
+    }
+}
+
+/// The parts of a setter that depend on `set_once`.
+struct SetterResult {
+    return_type: proc_macro2::TokenStream,
+    /// Runs before the field is stored.
+    check: proc_macro2::TokenStream,
+    result: proc_macro2::TokenStream,
+    /// Follows a call to another setter of the field.
+    forward: proc_macro2::TokenStream,
+}
+
+fn derive_setter_result(field: &Field, error_type: &proc_macro2::TokenStream) -> SetterResult {
+    let name = field.name;
+    let field_string = name.unraw().to_string();
+    match field.set_once {
+        None => SetterResult {
+            return_type: quote! {&mut Self},
+            check: quote! {},
+            result: quote! {self},
+            forward: quote! {},
+        },
+        Some(SetOnce::AtBuild) => {
+            let overwritten = overwritten_field();
+            SetterResult {
+                return_type: quote! {&mut Self},
+                check: quote! {
+                    if self.#name.is_some() {
+                        self.#overwritten.push(#field_string);
+                    }
+                },
+                result: quote! {self},
+                forward: quote! {},
+            }
+        }
+        Some(SetOnce::Strict) => SetterResult {
+            return_type: quote! {std::result::Result<&mut Self, #error_type>},
+            check: quote! {
+                if self.#name.is_some() {
+                    return std::result::Result::Err(#error_type::Overwritten {
+                        field: #field_string,
+                    });
+                }
+            },
+            result: quote! {std::result::Result::Ok(self)},
+            forward: quote! {?},
+        },
+    }
+}
+
+/// The type that the setter of a field without `each` stores, the type of its builder member for
+/// `field(ty = "...")`.
+fn get_setter_type<'a>(field: &'a Field) -> &'a syn::Type {
+    field
+        .custom
+        .as_ref()
+        .map_or(field.field_type, |custom| &custom.ty)
+}
+
+/// Whether the setter of `field` takes any implementation of a trait, e.g. a closure for
+/// `Box<dyn Fn()>`. Such members cannot be compared or printed.
+fn has_trait_object_setter(field: &Field) -> bool {
+    let setter_type = if field.repeated_name.is_some() {
+        get_angle_bracket_arg(field.field_type)
+    } else {
+        Some(field.field_type)
+    };
+    field.wrap
+        && matches!(
+            setter_type.and_then(get_angle_bracket_arg),
+            Some(syn::Type::TraitObject(_))
+        )
+}
+
+/// Derives `PartialEq` and implements `Debug` for the builder.
+///
+/// `Debug` prints unset members as `<unset>`, optional members that are set as `Some(...)` and
+/// members marked with `sensitive` as `"<redacted>"`.
+/// If members hold trait objects, `PartialEq` is left out and `Debug` prints their type instead of
+/// their value.
+fn derive_debug_impl(
+    builder_name: &syn::Ident,
+    fields: &[Field],
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let derives = if fields.iter().any(has_trait_object_setter) {
+        quote! {}
+    } else {
+        quote! {#[derive(PartialEq)]}
+    };
+
+    let debug_fields = fields.iter().map(|field| {
+        let name = field.name;
+        let name_str = name.unraw().to_string();
+        let setter_type = match &field.repeated_name {
+            Some(_) => get_angle_bracket_arg(field.field_type).unwrap_or(field.field_type),
+            None => field.field_type,
+        };
+        let type_name = quote!(#setter_type).to_string();
+        let value = if field.sub_builder.is_some() || field.custom.is_some() {
+            if field.sensitive {
+                quote! {&"<redacted>"}
+            } else {
+                quote! {&self.#name}
+            }
+        } else if field.repeated_name.is_some() && !field.optional {
+            if field.sensitive {
+                quote! {
+                    if self.#name.is_empty() {
+                        &self.#name as &dyn std::fmt::Debug
+                    } else {
+                        &"<redacted>"
+                    }
+                }
+            } else if has_trait_object_setter(field) {
+                quote! {&self.#name.iter().map(|_| #type_name).collect::<std::vec::Vec<_>>()}
+            } else {
+                quote! {&self.#name}
+            }
+        } else {
+            let set_arms = if field.sensitive {
+                quote! {std::option::Option::Some(_) => &"<redacted>",}
+            } else if has_trait_object_setter(field) && field.optional {
+                quote! {std::option::Option::Some(_) => &std::option::Option::Some(#type_name),}
+            } else if has_trait_object_setter(field) {
+                quote! {std::option::Option::Some(_) => &#type_name,}
+            } else if field.optional && !field.start_fn {
+                quote! {std::option::Option::Some(_) => &self.#name,}
+            } else {
+                quote! {std::option::Option::Some(value) => value,}
+            };
+            quote! {
+                match &self.#name {
+                    std::option::Option::None => &Unset as &dyn std::fmt::Debug,
+                    #set_arms
+                }
+            }
+        };
+        quote! {.field(#name_str, #value)}
+    });
+    let builder_name_str = builder_name.to_string();
+    let debug_impl = quote! {
+        impl std::fmt::Debug for #builder_name {
+            #[allow(deprecated)]
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                struct Unset;
+
+                impl std::fmt::Debug for Unset {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        f.write_str("<unset>")
+                    }
+                }
+
+                f.debug_struct(#builder_name_str)
+                    #(#debug_fields)*
+                    .finish()
+            }
+        }
+    };
+    (derives, debug_impl)
+}
+
+/// Returns the builder type of a struct type, e.g. `<config::Server as Buildable>::Builder` for
+/// `config::Server`. Naming the builder through the struct finds it wherever the struct is, also
+/// after `use config::Server`.
+fn get_sub_builder_type(t: &syn::Type) -> Option<syn::Type> {
+    match t {
+        syn::Type::Path(path)
+            if path.qself.is_none() && path.path.segments.last()?.arguments.is_empty() =>
+        {
+            let span = syn::spanned::Spanned::span(t);
+            syn::parse2(quote_spanned! {span=> <#t as ::robma_builder::Buildable>::Builder}).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Returns the doc comments of the field, or `default` if it has none.
+fn derive_field_docs(field: &Field, default: &str) -> proc_macro2::TokenStream {
+    if field.docs.is_empty() {
+        quote! {
+            #[doc = #default]
+        }
+    } else {
+        let docs = &field.docs;
+        quote! {
+            #(#[doc = #docs])*
+        }
+    }
+}
+
+fn derive_builder_docs(
+    name: &syn::Ident,
+    fields: &[Field],
+    target: &Target,
+) -> proc_macro2::TokenStream {
+    let list = |filter: &dyn Fn(&Field) -> bool| {
+        fields
+            .iter()
+            .filter(|field| filter(field))
+            .map(|field| format!("`{}`", field.name.unraw()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let is_required = |field: &Field| {
+        !field.optional && !field.flag && field.repeated_name.is_none() && field.custom.is_none()
+    };
+
+    let mut docs = vec![match target {
+        Target::Struct { .. } => format!("A builder for [`{}`].", name),
+        Target::Function { .. } => format!("A builder for the arguments of `{}`.", name.unraw()),
+    }];
+    for (title, fields) in &[
+        ("Start", list(&|field| field.start_fn)),
+        (
+            "Required",
+            list(&|field| is_required(field) && !field.start_fn),
+        ),
+        (
+            "Optional",
+            list(&|field| !is_required(field) && !field.start_fn),
+        ),
+    ] {
+        if !fields.is_empty() {
+            docs.push(String::new());
+            docs.push(format!("{} fields: {}", title, fields));
+        }
+    }
+
+    quote! {
+        #(#[doc = #docs])*
+    }
+}
+
+fn derive_setter_functions(fields: &[Field], error_name: &syn::Ident) -> proc_macro2::TokenStream {
+    let error_type = quote! {#error_name};
+    let setter_functions = fields
+        .iter()
+        .filter(|field| !field.start_fn)
+        .map(|field| {
+            let name = field.name;
+            let setter_name = &field.setter_name;
+            let vis = &field.vis;
+            let deprecated = &field.deprecated;
+            if let Some(repeated_name) = &field.repeated_name {
+                let repeated_type =
+                    get_angle_bracket_arg(field.field_type).expect("Expected vector type");
+                let docs =
+                    derive_field_docs(field, &format!("Appends an element to `{}`.", name.unraw()));
+                let SetterArgument {
+                    generics,
+                    argument_type,
+                    value,
+                } = derive_setter_argument(repeated_type, field.wrap, quote! {#repeated_type});
+                let values = if field.optional {
+                    quote! {self.#name.get_or_insert_with(std::vec::Vec::new)}
+                } else {
+                    quote! {self.#name}
+                };
+                let aliases = derive_alias_functions(
+                    field,
+                    &quote! {#vis},
+                    repeated_name,
+                    &generics,
+                    &quote! {x: #argument_type},
+                    &quote! {},
+                    &error_type,
+                );
+                quote! {
+                    #docs
+                    #(#deprecated)*
+                    #vis fn #repeated_name #generics(&mut self, x: #argument_type) -> &mut Self{
+                        #values.push(#value);
+                        self
+                    }
+
+                    #aliases
+                }
+            } else if let (Some(sub_builder), true) = (&field.sub_builder, field.flatten) {
+                let mut_name = format_ident!("{}_mut", name.unraw());
+                let docs = derive_field_docs(
+                    field,
+                    &format!("Returns the builder of `{}`.", name.unraw()),
+                );
+                quote! {
+                    #docs
+                    #(#deprecated)*
+                    #vis fn #mut_name(&mut self) -> &mut #sub_builder {
+                        &mut self.#name
+                    }
+                }
+            } else if let Some(sub_builder) = &field.sub_builder {
+                let mut_name = format_ident!("{}_mut", name.unraw());
+                let docs = derive_field_docs(
+                    field,
+                    &format!("Sets the fields of `{}` with its builder.", name.unraw()),
+                );
+                let mut_doc = format!("Returns the builder of `{}`.", name.unraw());
+                quote! {
+                    #docs
+                    #(#deprecated)*
+                    #vis fn #setter_name<F>(&mut self, f: F) -> &mut Self
+                    where
+                        F: std::ops::FnOnce(&mut #sub_builder) -> &mut #sub_builder,
+                    {
+                        f(&mut self.#name);
+                        self
+                    }
+
+                    #[doc = #mut_doc]
+                    #(#deprecated)*
+                    #vis fn #mut_name(&mut self) -> &mut #sub_builder {
+                        &mut self.#name
+                    }
+                }
+            } else if field.flag {
+                let if_name = format_ident!("{}_if", setter_name.unraw());
+                let docs = derive_field_docs(field, &format!("Sets `{}` to `true`.", name.unraw()));
+                let if_doc = format!("Sets `{}` to `condition`.", name.unraw());
+                let aliases = derive_alias_functions(
+                    field,
+                    &quote! {#vis},
+                    setter_name,
+                    &quote! {},
+                    &quote! {},
+                    &quote! {},
+                    &error_type,
+                );
+                let SetterResult {
+                    return_type,
+                    check,
+                    result,
+                    ..
+                } = derive_setter_result(field, &error_type);
+                quote! {
+                    #docs
+                    #(#deprecated)*
+                    #vis fn #setter_name(&mut self) -> #return_type {
+                        #check
+                        self.#name = std::option::Option::Some(true);
+                        #result
+                    }
+
+                    #[doc = #if_doc]
+                    #(#deprecated)*
+                    #vis fn #if_name(&mut self, condition: bool) -> #return_type {
+                        #check
+                        self.#name = std::option::Option::Some(condition);
+                        #result
+                    }
+
+                    #aliases
+                }
+            } else {
+                let field_type = get_setter_type(field);
+                let docs = derive_field_docs(field, &format!("Sets `{}`.", name.unraw()));
+                let SetterArgument {
+                    generics,
+                    argument_type,
+                    value,
+                } = derive_setter_argument(field_type, field.wrap, quote! {#field_type});
+                let value = if field.custom.is_some() {
+                    value
+                } else {
+                    quote! {std::option::Option::Some(#value)}
+                };
+                let aliases = derive_alias_functions(
+                    field,
+                    &quote! {#vis},
+                    setter_name,
+                    &generics,
+                    &quote! {x: #argument_type},
+                    &quote! {},
+                    &error_type,
+                );
+                let SetterResult {
+                    return_type,
+                    check,
+                    result,
+                    ..
+                } = derive_setter_result(field, &error_type);
+                quote! {
+                    #docs
+                    #(#deprecated)*
+                    #vis fn #setter_name #generics(&mut self, x: #argument_type) -> #return_type {
+                        #check
+                        self.#name = #value;
+                        #result
+                    }
+
+                    #aliases
+                }
+            }
+        })
+        .collect::<Vec<proc_macro2::TokenStream>>();
+
+    quote! {
+        #(#setter_functions)*
+    }
+}
+
+/// Derives the aliases of a setter, they call `setter` with the same arguments.
+fn derive_alias_functions(
+    field: &Field,
+    vis: &proc_macro2::TokenStream,
+    setter: &syn::Ident,
+    generics: &proc_macro2::TokenStream,
+    parameters: &proc_macro2::TokenStream,
+    where_clause: &proc_macro2::TokenStream,
+    error_type: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let deprecated = &field.deprecated;
+    let SetterResult {
+        return_type,
+        result,
+        forward,
+        ..
+    } = derive_setter_result(field, error_type);
+    let arguments = if parameters.is_empty() {
+        quote! {}
+    } else {
+        quote! {x}
+    };
+    let aliases = field.aliases.iter().map(|alias| {
+        let doc = format!("Alias of `{}()`.", setter.unraw());
+        quote! {
+            #[doc = #doc]
+            #(#deprecated)*
+            #vis fn #alias #generics(&mut self, #parameters) -> #return_type
+            #where_clause
+            {
+                #[allow(deprecated)]
+                self.#setter(#arguments)#forward;
+                #result
+            }
+        }
+    });
+    quote! {
+        #(#aliases)*
+    }
+}
+
+/// Derives the type that [`macro@build`] reaches through `robma_builder::__private::Literal`.
+/// It has a function for each member that calls the setter of the member with the value of
+/// the literal, and for each `Vec` member a function that appends a single element of a list
+/// literal, through the `each` setter if there is one.
+fn derive_literal_fields(
+    builder_name: &syn::Ident,
+    error_name: &syn::Ident,
+    vis: &syn::Visibility,
+    fields: &[Field],
+) -> proc_macro2::TokenStream {
+    let fields_name = format_ident!("__robma_builder_{}Fields", builder_name);
+    let error_type = quote! {#error_name};
+    let function = |field: &Field,
+                    name: &syn::Ident,
+                    generics: proc_macro2::TokenStream,
+                    argument_type: proc_macro2::TokenStream,
+                    set: proc_macro2::TokenStream| {
+        let vis = &field.vis;
+        let deprecated = &field.deprecated;
+        quote! {
+            #(#deprecated)*
+            #vis fn #name #generics(
+                builder: &mut #builder_name,
+                x: #argument_type,
+            ) -> std::result::Result<(), #error_name> {
+                #[allow(deprecated)]
+                {
+                    #set
+                }
+                std::result::Result::Ok(())
+            }
+        }
+    };
+    let functions = fields
+        .iter()
+        .filter(|field| field.sub_builder.is_none() && !field.start_fn)
+        .map(|field| {
+            let name = field.name;
+            let setter_name = &field.setter_name;
+            let each_name = format_ident!("__robma_builder_each_{}", name.unraw());
+            let forward = derive_setter_result(field, &error_type).forward;
+            if let Some(repeated_name) = &field.repeated_name {
+                let repeated_type =
+                    get_angle_bracket_arg(field.field_type).expect("Expected vector type");
+                let values = if field.optional {
+                    quote! {builder.#name.get_or_insert_with(std::vec::Vec::new)}
+                } else {
+                    quote! {builder.#name}
+                };
+                // The elements of a `Vec` value are stored as they are, the setter of
+                // `setter(wrap)` members would wrap them again.
+                let set = if field.wrap {
+                    quote! {#values.extend(x);}
+                } else {
+                    let values = if field.optional {
+                        quote! {#values;}
+                    } else {
+                        quote! {}
+                    };
+                    quote! {
+                        #values
+                        for x in x {
+                            builder.#repeated_name(x);
+                        }
+                    }
+                };
+                let SetterArgument {
+                    generics,
+                    argument_type,
+                    ..
+                } = derive_setter_argument(repeated_type, field.wrap, quote! {#repeated_type});
+                let each = if field.wrap {
+                    function(
+                        field,
+                        &each_name,
+                        generics,
+                        argument_type,
+                        quote! {builder.#repeated_name(x);},
+                    )
+                } else {
+                    function(
+                        field,
+                        &each_name,
+                        quote! {<X: std::convert::Into<#repeated_type>>},
+                        quote! {X},
+                        quote! {builder.#repeated_name(std::convert::Into::into(x));},
+                    )
+                };
+                let value = function(
+                    field,
+                    name,
+                    quote! {},
+                    quote! {std::vec::Vec<#repeated_type>},
+                    set,
+                );
+                quote! {
+                    #value
+                    #each
+                }
+            } else if field.flag {
+                let if_name = format_ident!("{}_if", setter_name.unraw());
+                function(
+                    field,
+                    name,
+                    quote! {<X: std::convert::Into<bool>>},
+                    quote! {X},
+                    quote! {builder.#if_name(std::convert::Into::into(x))#forward;},
+                )
+            } else if field.wrap {
+                let setter_type = get_setter_type(field);
+                let SetterArgument {
+                    generics,
+                    argument_type,
+                    ..
+                } = derive_setter_argument(setter_type, field.wrap, quote! {#setter_type});
+                function(
+                    field,
+                    name,
+                    generics,
+                    argument_type,
+                    quote! {builder.#setter_name(x)#forward;},
+                )
+            } else if let (Some(element_type), None) =
+                (get_vec_element(field.field_type), &field.custom)
+            {
+                // A list literal sets the member to an empty `Vec` with the setter and appends
+                // the elements to the storage.
+                let field_type = field.field_type;
+                let value = function(
+                    field,
+                    name,
+                    quote! {},
+                    quote! {#field_type},
+                    quote! {builder.#setter_name(x)#forward;},
+                );
+                let each = function(
+                    field,
+                    &each_name,
+                    quote! {<X: std::convert::Into<#element_type>>},
+                    quote! {X},
+                    quote! {
+                        builder
+                            .#name
+                            .get_or_insert_with(std::vec::Vec::new)
+                            .push(std::convert::Into::into(x));
+                    },
+                );
+                quote! {
+                    #value
+                    #each
+                }
+            } else {
+                let setter_type = get_setter_type(field);
+                function(
+                    field,
+                    name,
+                    quote! {<X: std::convert::Into<#setter_type>>},
+                    quote! {X},
+                    quote! {builder.#setter_name(std::convert::Into::into(x))#forward;},
+                )
+            }
+        });
+
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        #vis struct #fields_name;
+
+        impl ::robma_builder::__private::Literal for #builder_name {
+            type Fields = #fields_name;
+        }
+
+        #[allow(dead_code)]
+        impl #fields_name {
+            #(#functions)*
+        }
+    }
+}
+
+fn derive_getter_functions(fields: &[Field]) -> proc_macro2::TokenStream {
+    let getter_functions = fields.iter().map(|field| {
+        let name = field.name;
+        let vis = &field.vis;
+        let getter_name = format_ident!("get_{}", name.unraw());
+        let clear_name = format_ident!("clear_{}", name.unraw());
+        let clear_doc = format!("Unsets `{}`.", name.unraw());
+        if field.start_fn {
+            let declared_type = field.declared_type;
+            let docs = derive_field_docs(
+                field,
+                &format!(
+                    "Returns `{}` if `build()` did not take it yet.",
+                    name.unraw()
+                ),
+            );
+            quote! {
+                #docs
+                #vis fn #getter_name(&self) -> std::option::Option<&#declared_type> {
+                    self.#name.as_ref()
+                }
+            }
+        } else if field.repeated_name.is_some() {
+            let repeated_type =
+                get_angle_bracket_arg(field.field_type).expect("Expected vector type");
+            let docs = derive_field_docs(
+                field,
+                &format!("Returns the elements of `{}`.", name.unraw()),
+            );
+            let (values, clear) = if field.optional {
+                (
+                    quote! {self.#name.as_deref().unwrap_or(&[])},
+                    quote! {self.#name = std::option::Option::None;},
+                )
+            } else {
+                (quote! {&self.#name}, quote! {self.#name.clear();})
+            };
+            quote! {
+                #docs
+                #vis fn #getter_name(&self) -> &[#repeated_type] {
+                    #values
+                }
+
+                #[doc = #clear_doc]
+                #vis fn #clear_name(&mut self) -> &mut Self {
+                    #clear
+                    self
+                }
+            }
+        } else if let Some(custom) = &field.custom {
+            let ty = &custom.ty;
+            let docs = derive_field_docs(field, &format!("Returns `{}`.", name.unraw()));
+            quote! {
+                #docs
+                #vis fn #getter_name(&self) -> &#ty {
+                    &self.#name
+                }
+
+                #[doc = #clear_doc]
+                #vis fn #clear_name(&mut self) -> &mut Self {
+                    self.#name = std::default::Default::default();
+                    self
+                }
+            }
+        } else if let Some(sub_builder) = &field.sub_builder {
+            let docs = derive_field_docs(
+                field,
+                &format!("Returns the builder of `{}`.", name.unraw()),
+            );
+            quote! {
+                #docs
+                #vis fn #getter_name(&self) -> &#sub_builder {
+                    &self.#name
+                }
+
+                #[doc = #clear_doc]
+                #vis fn #clear_name(&mut self) -> &mut Self {
+                    self.#name.reset();
+                    self
+                }
+            }
+        } else {
+            let field_type = field.field_type;
+            let docs =
+                derive_field_docs(field, &format!("Returns `{}` if it is set.", name.unraw()));
+            quote! {
+                #docs
+                #vis fn #getter_name(&self) -> std::option::Option<&#field_type> {
+                    self.#name.as_ref()
+                }
+
+                #[doc = #clear_doc]
+                #vis fn #clear_name(&mut self) -> &mut Self {
+                    self.#name = std::option::Option::None;
+                    self
+                }
+            }
+        }
+    });
+
+    quote! {
+        #(#getter_functions)*
+    }
+}
+
+fn derive_reset_function(vis: &syn::Visibility, fields: &[Field]) -> proc_macro2::TokenStream {
+    let reset_statements = fields.iter().filter(|field| !field.start_fn).map(|field| {
+        let name = field.name;
+        if field.sub_builder.is_some() {
+            quote! {
+                self.#name.reset();
+            }
+        } else if field.custom.is_some() {
+            quote! {
+                self.#name = std::default::Default::default();
+            }
+        } else if field.repeated_name.is_some() && !field.optional {
+            quote! {
+                self.#name.clear();
+            }
+        } else {
+            quote! {
+                self.#name = std::option::Option::None;
+            }
+        }
+    });
+
+    let reset_overwritten = derive_overwritten(fields, |overwritten| {
+        quote! {
+            self.#overwritten.clear();
+        }
+    });
+
+    let doc = if fields.iter().any(|field| field.start_fn) {
+        "Unsets all fields, except for the arguments of the function that created the builder."
+    } else {
+        "Unsets all fields."
+    };
+    quote! {
+        #[doc = #doc]
+        #vis fn reset(&mut self) -> &mut Self {
+            #(#reset_statements)*
+            #reset_overwritten
+            self
+        }
+    }
+}
+
+/// An expression that tells whether a field of `self` is set.
+fn derive_is_set(field: &Field) -> proc_macro2::TokenStream {
+    let name = field.name;
+    if field.sub_builder.is_some() {
+        quote! {self.#name.missing_fields().is_empty()}
+    } else if let Some(custom) = &field.custom {
+        let ty = &custom.ty;
+        quote! {self.#name != <#ty as std::default::Default>::default()}
+    } else if field.repeated_name.is_some() && !field.optional {
+        quote! {!self.#name.is_empty()}
+    } else {
+        quote! {self.#name.is_some()}
+    }
+}
+
+/// Checks the groups and the `set_once`, `requires` and `conflicts_with` attributes of the
+/// fields.
+fn derive_rule_checks(
+    error_name: &syn::Ident,
+    groups: &[Group],
+    fields: &[Field],
+) -> proc_macro2::TokenStream {
+    let error = |error: proc_macro2::TokenStream| {
+        quote! {
+            return std::result::Result::Err(std::boxed::Box::new(#error_name::#error));
+        }
+    };
+    let find = |name: &syn::LitStr| {
+        fields
+            .iter()
+            .find(|field| field.name.unraw() == name.value())
+            .expect("Expected checked field name")
+    };
+
+    let mut checks = Vec::new();
+    checks.extend(derive_overwritten(fields, |overwritten| {
+        let error = error(quote! {Overwritten { field }});
+        quote! {
+            if let std::option::Option::Some(&field) = self.#overwritten.first() {
+                #error
+            }
+        }
+    }));
+    for field in fields {
+        let field_string = field.name.unraw().to_string();
+        let is_set = derive_is_set(field);
+        for other in &field.conflicts_with {
+            let other_is_set = derive_is_set(find(other));
+            let error = error(quote! {Conflict {
+                field: #field_string,
+                other: #other,
+            }});
+            checks.push(quote! {
+                if #is_set && #other_is_set {
+                    #error
+                }
+            });
+        }
+        for required in &field.requires {
+            let required_is_set = derive_is_set(find(required));
+            let error = error(quote! {Requires {
+                field: #field_string,
+                required: #required,
+            }});
+            checks.push(quote! {
+                if #is_set && !(#required_is_set) {
+                    #error
+                }
+            });
+        }
+
+        let mut count_checks = Vec::new();
+        if let Some(min) = field.min {
+            let error = error(quote! {TooFew {
+                field: #field_string,
+                min: #min,
+                count: values.len(),
+            }});
+            count_checks.push(quote! {
+                if values.len() < #min {
+                    #error
+                }
+            });
+        }
+        if let Some(max) = field.max {
+            let error = error(quote! {TooMany {
+                field: #field_string,
+                max: #max,
+                count: values.len(),
+            }});
+            count_checks.push(quote! {
+                if values.len() > #max {
+                    #error
+                }
+            });
+        }
+        if field.unique {
+            let error = error(quote! {Duplicate {
+                field: #field_string,
+                index,
+            }});
+            count_checks.push(quote! {
+                let mut seen = std::collections::HashSet::new();
+                for (index, value) in values.iter().enumerate() {
+                    if !seen.insert(value) {
+                        #error
+                    }
+                }
+            });
+        }
+        if !count_checks.is_empty() {
+            let name = field.name;
+            // Optional repeated fields are only checked once they are set.
+            checks.push(if field.optional {
+                quote! {
+                    if let std::option::Option::Some(values) = &self.#name {
+                        #(#count_checks)*
+                    }
+                }
+            } else {
+                quote! {
+                    {
+                        let values = &self.#name;
+                        #(#count_checks)*
+                    }
+                }
+            });
+        }
+    }
+
+    for group in groups {
+        let group_string = group.name.to_string();
+        let members = fields
+            .iter()
+            .filter(|field| field.group.iter().any(|name| group.name == name.value()))
+            .collect::<Vec<_>>();
+        let member_strings = members
+            .iter()
+            .map(|field| field.name.unraw().to_string())
+            .collect::<Vec<_>>();
+        let member_is_set = members.iter().map(|field| derive_is_set(field));
+
+        let conflict_check = if !group.exclusive {
+            quote! {}
+        } else {
+            let error = error(quote! {GroupConflict {
+                group: #group_string,
+                fields: set,
+            }});
+            quote! {
+                if set.len() > 1 {
+                    #error
+                }
+            }
+        };
+        let empty_check = if !group.required {
+            quote! {}
+        } else {
+            let error = error(quote! {GroupEmpty {
+                group: #group_string,
+                fields: &[#(#member_strings),*],
+            }});
+            quote! {
+                if set.is_empty() {
+                    #error
+                }
+            }
+        };
+        checks.push(quote! {
+            {
+                let mut set: std::vec::Vec<&'static str> = std::vec::Vec::new();
+                #(
+                    if #member_is_set {
+                        set.push(#member_strings);
+                    }
+                )*
+                #empty_check
+                #conflict_check
+            }
+        });
+    }
+
+    quote! {
+        #(#checks)*
+    }
+}
+
+fn derive_build_function(
+    name: &syn::Ident,
+    build_fn_name: &syn::Ident,
+    error_name: &syn::Ident,
+    vis: &syn::Visibility,
+    groups: &[Group],
+    fields: &[Field],
+    target: &Target,
+) -> proc_macro2::TokenStream {
+    let rule_checks = derive_rule_checks(error_name, groups, fields);
+
+    let field_values: Vec<proc_macro2::TokenStream> = fields
+        .iter()
+        .filter(|field| field.computed.is_none())
+        .map(|field| {
+            let field_name = field.name;
+            let field_string = field_name.unraw().to_string();
+            if let Some(custom) = &field.custom {
+                let declared_type = field.declared_type;
+                let build = &custom.build;
+                quote! {
+                    {
+                        let value: #declared_type = #build;
+                        value
+                    }
+                }
+            } else if field.start_fn {
+                quote! {
+                    self.#field_name.take().ok_or(
+                        ::robma_builder::UninitializedFieldError::new(#field_string)
+                    )?
+                }
+            } else if field.flatten {
+                quote! {
+                    ::robma_builder::Builder::build(&mut self.#field_name)?
+                }
+            } else if field.sub_builder.is_some() {
+                quote! {
+                    ::robma_builder::Builder::build(&mut self.#field_name).map_err(|error| {
+                        match error.downcast::<::robma_builder::UninitializedFieldError>() {
+                            std::result::Result::Ok(error) => {
+                                std::boxed::Box::new(error.in_member(#field_string))
+                                    as std::boxed::Box<dyn std::error::Error>
+                            }
+                            std::result::Result::Err(error) => std::boxed::Box::new(#error_name::Nested {
+                                field: #field_string,
+                                source: error,
+                            }),
+                        }
+                    })?
+                }
+            } else if field.flag && !field.optional {
+                quote! {
+                    self.#field_name.take().unwrap_or(false)
+                }
+            } else if !field.optional && field.repeated_name.is_none() {
+                quote! {
+                    self.#field_name.take().ok_or(
+                        ::robma_builder::UninitializedFieldError::new(#field_string)
+                    )?
+                }
+            } else if field.repeated_name.is_some() {
+                quote! {
+                    self.#field_name.clone()
+                }
+            } else {
+                quote! {
+                    self.#field_name.take()
+                }
+            }
+        })
+        .collect();
+
+    // The fields are resolved into variables of the same name, so that computed fields can use
+    // them.
+    let stored_names = fields
+        .iter()
+        .filter(|field| field.computed.is_none())
+        .map(|field| field.name);
+    let computed_values = fields.iter().filter_map(|field| {
+        let field_name = field.name;
+        let declared_type = field.declared_type;
+        let value = match field.computed.as_ref()? {
+            Computed::Expr(expr) => quote! {#expr},
+            Computed::With(function) => {
+                let arguments = fields
+                    .iter()
+                    .filter(|field| field.computed.is_none())
+                    .map(|field| field.name);
+                quote! {#function(#(&#arguments),*)}
+            }
+        };
+        Some(quote! {
+            let #field_name: #declared_type = #value;
+        })
+    });
+    let resolve_fields = quote! {
+        #(let #stored_names = #field_values;)*
+        #(#computed_values)*
+    };
+    let field_names = fields.iter().map(|field| field.name);
+
+    let (output, value, doc) = match target {
+        Target::Struct { .. } => (
+            quote! {#name},
+            quote! {
+                #name {
+                    #(#field_names),*
+                }
+            },
+            format!(
+                "Creates a [`{}`], fails if a required field is not set.",
+                name
+            ),
+        ),
+        Target::Function {
+            self_type,
+            implementation,
+            output,
+            ..
+        } => {
+            let function = match self_type {
+                Some(self_type) => quote! {<#self_type>::#implementation},
+                None => quote! {#implementation},
+            };
+            (
+                quote! {#output},
+                quote! {
+                    #function(#(#field_names),*)
+                },
+                format!(
+                    "Calls `{}` with the arguments, fails if a required argument is not set.",
+                    name.unraw()
+                ),
+            )
+        }
+    };
+
+    quote! {
+        #[doc = #doc]
+        #[allow(deprecated)]
+        #vis fn #build_fn_name(&mut self) -> std::result::Result<#output, std::boxed::Box<dyn std::error::Error>> {
+            #rule_checks
+            #resolve_fields
+            std::result::Result::Ok(#value)
+        }
+    }
+}
+
+fn derive_merge_functions(
+    builder_name: &syn::Ident,
+    vis: &syn::Visibility,
+    fields: &[Field],
+) -> proc_macro2::TokenStream {
+    let merge_statements = fields.iter().map(|field| {
+        let name = field.name;
+        if field.sub_builder.is_some() {
+            quote! {
+                self.#name.merge(other.#name);
+            }
+        } else if let Some(custom) = &field.custom {
+            let ty = &custom.ty;
+            quote! {
+                if other.#name != <#ty as std::default::Default>::default() {
+                    self.#name = other.#name;
+                }
+            }
+        } else if field.set_once.is_some() {
+            let overwritten = overwritten_field();
+            let field_string = name.unraw().to_string();
+            quote! {
+                if other.#name.is_some() {
+                    if self.#name.is_some() {
+                        self.#overwritten.push(#field_string);
+                    }
+                    self.#name = other.#name;
+                }
+            }
+        } else if field.repeated_name.is_none()
+            || (field.optional && field.merge == MergeStrategy::Replace)
+        {
+            quote! {
+                if other.#name.is_some() {
+                    self.#name = other.#name;
+                }
+            }
+        } else if field.optional {
+            quote! {
+                if let std::option::Option::Some(values) = other.#name {
+                    self.#name.get_or_insert_with(std::vec::Vec::new).extend(values);
+                }
+            }
+        } else if field.merge == MergeStrategy::Append {
+            quote! {
+                self.#name.extend(other.#name);
+            }
+        } else {
+            quote! {
+                if !other.#name.is_empty() {
+                    self.#name = other.#name;
+                }
+            }
+        }
+    });
+
+    let or_else_statements = fields.iter().filter(|field| !field.start_fn).map(|field| {
+        let name = field.name;
+        if field.sub_builder.is_some() {
+            quote! {
+                self.#name.or_else(other.#name);
+            }
+        } else if let Some(custom) = &field.custom {
+            let ty = &custom.ty;
+            quote! {
+                if self.#name == <#ty as std::default::Default>::default() {
+                    self.#name = other.#name;
+                }
+            }
+        } else if field.repeated_name.is_none() || field.optional {
+            quote! {
+                if self.#name.is_none() {
+                    self.#name = other.#name;
+                }
+            }
+        } else {
+            quote! {
+                if self.#name.is_empty() {
+                    self.#name = other.#name;
+                }
+            }
+        }
+    });
+
+    let merge_overwritten = derive_overwritten(fields, |overwritten| {
+        quote! {
+            self.#overwritten.extend(other.#overwritten);
+        }
+    });
+
+    quote! {
+        /// Overrides the fields of this builder with all fields that are set in `other`.
+        #vis fn merge(&mut self, other: #builder_name) -> &mut Self {
+            #(#merge_statements)*
+            #merge_overwritten
+            self
+        }
+
+        /// Sets all fields that are not set yet to the fields of `other`.
+        #vis fn or_else(&mut self, other: #builder_name) -> &mut Self {
+            #(#or_else_statements)*
+            self
+        }
+    }
+}
+
+fn derive_env_functions(
+    new_builder: &proc_macro2::TokenStream,
+    start_parameters: &proc_macro2::TokenStream,
+    error_name: &syn::Ident,
+    vis: &syn::Visibility,
+    fields: &[Field],
+) -> proc_macro2::TokenStream {
+    if !fields.iter().any(has_env) {
+        return quote! {};
+    }
+
+    let env_statements = fields.iter().filter_map(|field| {
+        let field_name = field.name;
+        let field_string = field_name.unraw().to_string();
+        if field.inner_env {
+            return Some(quote! {
+                if let std::result::Result::Err(error) = self.#field_name.apply_env() {
+                    return std::result::Result::Err(#error_name::Nested {
+                        field: #field_string,
+                        source: std::boxed::Box::new(error),
+                    });
+                }
+            });
+        }
+        let variable = field.env_variable.as_ref()?;
+        let parse_error = quote! {
+            |error| #error_name::Env {
+                variable: #variable,
+                field: #field_string,
+                message: std::string::ToString::to_string(&error),
+            }
+        };
+
+        let assignment = if field.repeated_name.is_some() {
+            let separator = &field.env_separator;
+            let assignment =
+                derive_parse_assignment(field, quote! {item}, &parse_error, error_name);
+            let initialize = if field.optional {
+                quote! {self.#field_name.get_or_insert_with(std::vec::Vec::new);}
+            } else {
+                quote! {}
+            };
+            quote! {
+                #initialize
+                for item in value.split(#separator).filter(|item| !item.is_empty()) {
+                    #assignment
+                }
+            }
+        } else {
+            derive_parse_assignment(field, quote! {value}, &parse_error, error_name)
+        };
+
+        Some(quote! {
+            match std::env::var(#variable) {
+                std::result::Result::Ok(value) => {
+                    #assignment
+                }
+                std::result::Result::Err(std::env::VarError::NotPresent) => {}
+                std::result::Result::Err(error) => {
+                    return std::result::Result::Err((#parse_error)(error));
+                }
+            }
+        })
+    });
+
+    quote! {
+        /// Creates a builder from the environment variables of the fields.
+        #vis fn from_env(#start_parameters) -> std::result::Result<Self, #error_name> {
+            let mut builder = #new_builder;
+            builder.apply_env()?;
+            std::result::Result::Ok(builder)
+        }
+
+        /// Sets all fields whose environment variable is present.
+        #[allow(deprecated)]
+        #vis fn apply_env(&mut self) -> std::result::Result<&mut Self, #error_name> {
+            #(#env_statements)*
+            std::result::Result::Ok(self)
+        }
+    }
+}
+
+/// Whether `apply_env()` reads the field, directly or through the builder of the field.
+fn has_env(field: &Field) -> bool {
+    field.env_variable.is_some() || field.inner_env
+}
+
+/// Parses `value` with `FromStr` and stores it in the field or appends it to a repeated field.
+fn derive_parse_assignment(
+    field: &Field,
+    value: proc_macro2::TokenStream,
+    map_error: &proc_macro2::TokenStream,
+    error_name: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    if let Some(repeated_name) = &field.repeated_name {
+        let repeated_type = get_angle_bracket_arg(field.field_type).expect("Expected vector type");
+        quote! {
+            self.#repeated_name(#value.parse::<#repeated_type>().map_err(#map_error)?);
+        }
+    } else {
+        let field_name = field.name;
+        let field_type = get_setter_type(field);
+        let SetterResult { check, .. } = derive_setter_result(field, &quote! {#error_name});
+        let value = quote! {#value.parse::<#field_type>().map_err(#map_error)?};
+        if field.custom.is_some() {
+            quote! {
+                self.#field_name = #value;
+            }
+        } else {
+            quote! {
+                #check
+                self.#field_name = std::option::Option::Some(#value);
+            }
+        }
+    }
+}
+
+fn derive_set_by_name_functions(
+    new_builder: &proc_macro2::TokenStream,
+    start_parameters: &proc_macro2::TokenStream,
+    error_name: &syn::Ident,
+    vis: &syn::Visibility,
+    fields: &[Field],
+) -> proc_macro2::TokenStream {
+    let match_arms = fields
+        .iter()
+        .filter(|field| field.sub_builder.is_none() && !field.start_fn)
+        .map(|field| {
+            let field_string = field.name.unraw().to_string();
+            let error_value = if field.sensitive {
+                quote! {"<redacted>"}
+            } else {
+                quote! {value}
+            };
+            let parse_error = quote! {
+                |error| #error_name::Parse {
+                    field: #field_string,
+                    value: std::string::ToString::to_string(#error_value),
+                    message: std::string::ToString::to_string(&error),
+                }
+            };
+            let assignment =
+                derive_parse_assignment(field, quote! {value}, &parse_error, error_name);
+            quote! {
+                #field_string => {
+                    #assignment
+                }
+            }
+        });
+
+    let nested_arms = fields
+        .iter()
+        .filter(|field| field.sub_builder.is_some() && !field.flatten)
+        .map(|field| {
+        let field_name = field.name;
+        let field_string = field_name.unraw().to_string();
+        quote! {
+            #field_string => {
+                if let std::result::Result::Err(error) = self.#field_name.set_by_name(rest, value) {
+                    return std::result::Result::Err(#error_name::Nested {
+                        field: #field_string,
+                        source: std::boxed::Box::new(error),
+                    });
+                }
+                return std::result::Result::Ok(self);
+            }
+        }
+    });
+
+    // The flattened builders need `#[builder(set_by_name)]` as well, a missing `set_by_name` is
+    // reported at the type of the member.
+    let flatten_attempts = fields.iter().filter(|field| field.flatten).map(|field| {
+        let field_name = field.name;
+        let field_string = field_name.unraw().to_string();
+        let span = syn::spanned::Spanned::span(field.field_type);
+        let set_by_name = quote_spanned! {span=>
+            self.#field_name.set_by_name(field, value)
+        };
+        quote! {
+            match #set_by_name.map(|_| ()) {
+                std::result::Result::Ok(()) => return std::result::Result::Ok(self),
+                std::result::Result::Err(#error_name::UnknownField { .. }) => {}
+                std::result::Result::Err(error) => {
+                    return std::result::Result::Err(#error_name::Nested {
+                        field: #field_string,
+                        source: std::boxed::Box::new(error),
+                    });
+                }
+            }
+        }
+    });
+
+    quote! {
+        /// Parses `value` and sets the field named `field`, repeated fields get the value appended.
+        ///
+        /// The fields of sub-builders are set by their path, e.g. `server.port`,
+        /// the fields of flattened builders by their own name.
+        #[allow(deprecated)]
+        #vis fn set_by_name(
+            &mut self,
+            field: &str,
+            value: &str,
+        ) -> std::result::Result<&mut Self, #error_name> {
+            if let std::option::Option::Some(index) = field.find('.') {
+                let rest = &field[index + 1..];
+                match &field[..index] {
+                    #(#nested_arms)*
+                    _ => {}
+                }
+            }
+            match field {
+                #(#match_arms)*
+                _ => {
+                    #(#flatten_attempts)*
+                    return std::result::Result::Err(#error_name::UnknownField {
+                        field: std::string::ToString::to_string(field),
+                    });
+                }
+            }
+            std::result::Result::Ok(self)
+        }
+
+        /// Creates a builder from `(field, value)` pairs, see `set_by_name`.
+        #vis fn from_pairs<I, K, V>(
+            #start_parameters
+            pairs: I,
+        ) -> std::result::Result<Self, #error_name>
+        where
+            I: std::iter::IntoIterator<Item = (K, V)>,
+            K: std::convert::AsRef<str>,
+            V: std::convert::AsRef<str>,
+        {
+            let mut builder = #new_builder;
+            for (field, value) in pairs {
+                builder.set_by_name(field.as_ref(), value.as_ref())?;
+            }
+            std::result::Result::Ok(builder)
+        }
+    }
+}
+
+/// Returns the element type of a `Vec`.
+fn get_vec_element(t: &syn::Type) -> Option<&syn::Type> {
+    match t {
+        syn::Type::Path(path) if path.path.segments.last()?.ident == "Vec" => {
+            get_angle_bracket_arg(t)
+        }
+        _ => None,
+    }
+}
+
+fn is_bool(t: &syn::Type) -> bool {
+    match t {
+        syn::Type::Path(t) => t.qself.is_none() && t.path.is_ident("bool"),
+        _ => false,
+    }
+}
+
+/// The command line flag of a field, e.g. `--current-dir` for `current_dir`.
+fn get_flag_name(field: &Field) -> String {
+    let name = field.repeated_name.as_ref().unwrap_or(field.name);
+    format!("--{}", name.unraw().to_string().replace('_', "-"))
+}
+
+/// Returns the start of the help text and the options of the own members, each with its description.
+fn derive_help_text(struct_docs: &[String], fields: &[Field]) -> (String, Vec<(String, String)>) {
+    let options = fields
+        .iter()
+        .filter(|field| field.sub_builder.is_none() && !field.start_fn)
+        .map(|field| {
+            let flag = get_flag_name(field);
+            let value_name = flag[2..].replace('-', "_").to_uppercase();
+            let option = if is_bool(field.field_type) && field.custom.is_none() {
+                format!("    {}", flag)
+            } else if field.repeated_name.is_some() {
+                format!("    {} <{}>...", flag, value_name)
+            } else {
+                format!("    {} <{}>", flag, value_name)
+            };
+            let mut description = field.docs.join(" ");
+            if !field.optional
+                && field.repeated_name.is_none()
+                && field.custom.is_none()
+                && !is_bool(field.field_type)
+            {
+                description.push_str(" (required)");
+            }
+            (option, description.trim().to_owned())
+        })
+        .collect();
+
+    let mut header = String::new();
+    if !struct_docs.is_empty() {
+        header.push_str(struct_docs.join("\n").trim());
+        header.push_str("\n\n");
+    }
+    header.push_str("Options:\n");
+    (header, options)
+}
+
+fn derive_cli_functions(
+    new_builder: &proc_macro2::TokenStream,
+    start_parameters: &proc_macro2::TokenStream,
+    error_name: &syn::Ident,
+    vis: &syn::Visibility,
+    struct_docs: &[String],
+    fields: &[Field],
+) -> proc_macro2::TokenStream {
+    let (header, options) = derive_help_text(struct_docs, fields);
+    let options = options
+        .iter()
+        .map(|(option, description)| quote! {(#option, #description)});
+
+    let match_arms = fields
+        .iter()
+        .filter(|field| field.sub_builder.is_none() && !field.start_fn)
+        .map(|field| {
+        let field_name = field.name;
+        let field_string = field_name.unraw().to_string();
+        let flag = get_flag_name(field);
+        let set_value = quote! {
+            let value = match inline_value {
+                std::option::Option::Some(value) => std::string::ToString::to_string(value),
+                std::option::Option::None => args.next().ok_or_else(|| #error_name::MissingValue {
+                    argument: std::string::ToString::to_string(flag),
+                })?,
+            };
+            self.set_by_name(#field_string, &value)?;
+        };
+        if is_bool(field.field_type) && field.repeated_name.is_none() && field.custom.is_none() {
+            quote! {
+                #flag => {
+                    if inline_value.is_none() {
+                        // `set_by_name` applies the `set_once` check of the field.
+                        self.set_by_name(#field_string, "true")?;
+                    } else {
+                        #set_value
+                    }
+                }
+            }
+        } else {
+            quote! {
+                #flag => {
+                    #set_value
+                }
+            }
+        }
+    });
+
+    // The flattened builders need `#[builder(cli)]` as well, a missing function is reported at the
+    // type of the member.
+    let flattened = fields
+        .iter()
+        .filter(|field| field.flatten)
+        .collect::<Vec<_>>();
+    let flatten_options = flattened.iter().map(|field| {
+        let sub_builder = &field.sub_builder;
+        let span = syn::spanned::Spanned::span(field.field_type);
+        quote_spanned! {span=>
+            <#sub_builder>::__robma_builder_cli_options(options);
+        }
+    });
+    let flatten_attempts = flattened.iter().map(|field| {
+        let field_name = field.name;
+        let field_string = field_name.unraw().to_string();
+        let span = syn::spanned::Spanned::span(field.field_type);
+        let parse_arg = quote_spanned! {span=>
+            self.#field_name.__robma_builder_parse_arg(flag, inline_value, args)
+        };
+        quote! {
+            match #parse_arg {
+                std::result::Result::Ok(false) => {}
+                std::result::Result::Ok(true) => return std::result::Result::Ok(true),
+                std::result::Result::Err(error) => {
+                    return std::result::Result::Err(#error_name::Nested {
+                        field: #field_string,
+                        source: std::boxed::Box::new(error),
+                    });
+                }
+            }
+        }
+    });
+    let flatten_defaults = flattened.iter().map(|field| {
+        let field_name = field.name;
+        let span = syn::spanned::Spanned::span(field.field_type);
+        quote_spanned! {span=>
+            self.#field_name.__robma_builder_cli_defaults();
+        }
+    });
+
+    let flag_defaults = fields
+        .iter()
+        .filter(|field| {
+            is_bool(field.field_type)
+                && !field.optional
+                && field.repeated_name.is_none()
+                && field.sub_builder.is_none()
+                && field.custom.is_none()
+                && !field.start_fn
+        })
+        .map(|field| {
+            let field_name = field.name;
+            quote! {
+                if self.#field_name.is_none() {
+                    self.#field_name = std::option::Option::Some(false);
+                }
+            }
+        });
+
+    quote! {
+        /// Returns the help text of the command line arguments.
+        #vis fn help() -> std::string::String {
+            let mut options = std::vec::Vec::new();
+            Self::__robma_builder_cli_options(&mut options);
+            options.push(("-h, --help", "Print this help"));
+            ::robma_builder::__private::help_text(#header, &options)
+        }
+
+        /// Creates a builder from command line arguments, without the name of the program.
+        #vis fn parse_args<I>(#start_parameters args: I) -> std::result::Result<Self, #error_name>
+        where
+            I: std::iter::IntoIterator<Item = std::string::String>,
+        {
+            let mut builder = #new_builder;
+            let mut args = args.into_iter();
+            while let std::option::Option::Some(arg) = args.next() {
+                let (flag, inline_value) = match arg.find('=') {
+                    std::option::Option::Some(index) if arg.starts_with("--") => {
+                        (&arg[..index], std::option::Option::Some(&arg[index + 1..]))
+                    }
+                    _ => (arg.as_str(), std::option::Option::None),
+                };
+                if flag == "-h" || flag == "--help" {
+                    return std::result::Result::Err(#error_name::Help { text: Self::help() });
+                }
+                if !builder.__robma_builder_parse_arg(flag, inline_value, &mut args)? {
+                    return std::result::Result::Err(#error_name::UnknownArgument {
+                        argument: std::clone::Clone::clone(&arg),
+                    });
+                }
+            }
+            builder.__robma_builder_cli_defaults();
+            std::result::Result::Ok(builder)
+        }
+
+        /// Appends the options of the help text, also the ones of flattened builders.
+        #[doc(hidden)]
+        #vis fn __robma_builder_cli_options(options: &mut std::vec::Vec<(&'static str, &'static str)>) {
+            options.extend_from_slice(&[#(#options),*]);
+            #(#flatten_options)*
+        }
+
+        /// Sets the member of the command line flag `flag`, or the one of a flattened builder,
+        /// and returns whether the flag is known. A value that is not given inline is taken from `args`.
+        #[doc(hidden)]
+        #[allow(deprecated)]
+        #vis fn __robma_builder_parse_arg(
+            &mut self,
+            flag: &str,
+            inline_value: std::option::Option<&str>,
+            args: &mut dyn std::iter::Iterator<Item = std::string::String>,
+        ) -> std::result::Result<bool, #error_name> {
+            match flag {
+                #(#match_arms)*
+                _ => {
+                    #(#flatten_attempts)*
+                    return std::result::Result::Ok(false);
+                }
+            }
+            std::result::Result::Ok(true)
+        }
+
+        /// Sets the `bool` members that were not given on the command line to `false`.
+        #[doc(hidden)]
+        #vis fn __robma_builder_cli_defaults(&mut self) {
+            #(#flag_defaults)*
+            #(#flatten_defaults)*
+        }
+    }
+}
+
+fn derive_error_type(error_name: &syn::Ident, vis: &syn::Visibility) -> proc_macro2::TokenStream {
+    quote! {
+        /// The errors of the builder.
+        #vis type #error_name = ::robma_builder::BuilderError;
+    }
+}
+
+fn derive_field_info_functions(
+    vis: &syn::Visibility,
+    fields: &[Field],
+) -> proc_macro2::TokenStream {
+    let field_infos = fields.iter().map(|field| {
+        let field_string = field.name.unraw().to_string();
+        let declared_type = type_to_string(field.declared_type);
+        let kind = if field.repeated_name.is_some() {
+            quote! {Repeated}
+        } else if field.optional || field.flag || field.custom.is_some() {
+            quote! {Optional}
+        } else {
+            quote! {Required}
+        };
+        let each = match &field.repeated_name {
+            Some(repeated_name) => {
+                let repeated_string = repeated_name.to_string();
+                quote! {std::option::Option::Some(#repeated_string)}
+            }
+            None => quote! {std::option::Option::None},
+        };
+        let doc = field.docs.join("\n");
+        quote! {
+            ::robma_builder::FieldInfo {
+                name: #field_string,
+                ty: #declared_type,
+                kind: ::robma_builder::FieldKind::#kind,
+                each: #each,
+                doc: #doc,
+            }
+        }
+    });
+
+    let is_set_functions = fields.iter().filter(|field| !field.start_fn).map(|field| {
+        let name = field.name;
+        let function_name = format_ident!("is_{}_set", name.unraw());
+        let doc = if field.sub_builder.is_some() {
+            format!(
+                "Returns whether all required fields of `{}` are set.",
+                name.unraw()
+            )
+        } else {
+            format!("Returns whether `{}` is set.", name.unraw())
+        };
+        let is_set = derive_is_set(field);
+        quote! {
+            #[doc = #doc]
+            #vis fn #function_name(&self) -> bool {
+                #is_set
+            }
+        }
+    });
+
+    let missing_fields = fields
+        .iter()
+        .filter(|field| {
+            (!field.optional || field.start_fn)
+                && !field.flag
+                && field.repeated_name.is_none()
+                && field.custom.is_none()
+        })
+        .map(|field| {
+            let name = field.name;
+            let field_string = name.unraw().to_string();
+            if field.flatten {
+                quote! {
+                    missing.extend(self.#name.missing_fields());
+                }
+            } else if field.sub_builder.is_some() {
+                quote! {
+                    if !self.#name.missing_fields().is_empty() {
+                        missing.push(#field_string);
+                    }
+                }
+            } else {
+                quote! {
+                    if self.#name.is_none() {
+                        missing.push(#field_string);
+                    }
+                }
+            }
+        });
+
+    quote! {
+        /// Describes all fields of the builder.
+        #vis const FIELDS: &'static [::robma_builder::FieldInfo] = &[
+            #(#field_infos),*
+        ];
+
+        #(#is_set_functions)*
+
+        /// Returns the names of all required fields that are not set,
+        /// including sub-builders with a required field that is not set
+        /// and the required fields of flattened builders.
+        #vis fn missing_fields(&self) -> std::vec::Vec<&'static str> {
+            let mut missing = std::vec::Vec::new();
+            #(#missing_fields)*
+            missing
+        }
+    }
+}
+
+/// The name of the hidden macro that forwards the setters of `name` to a flattening builder.
+fn get_flatten_macro_name(name: &syn::Ident) -> syn::Ident {
+    format_ident!("__robma_builder_flatten_{}", name)
+}
+
+/// Derives a macro that generates the setters of this builder on a builder with a flattened field of this type.
+///
+/// It is only derived for structs marked with `#[builder(flattenable)]`.
+/// The macro is imported under the name of the struct, so it is found wherever the struct is, and
+/// invoked as `Struct!(vis [StructBuilder] OuterBuilder field)`. The setters of the outer builder
+/// cannot name the types of this module, so they call the implementations of the hidden traits of
+/// the runtime crate for the member at index `I`, written as `[(); I]`, and infer their types.
+/// The types are generic parameters of these traits, so the implementations are only as visible as
+/// the types.
+fn derive_flatten_macro(
+    name: &syn::Ident,
+    builder_name: &syn::Ident,
+    error_name: &syn::Ident,
+    fields: &[Field],
+) -> proc_macro2::TokenStream {
+    let macro_name = get_flatten_macro_name(name);
+    let setter_trait = quote! {::robma_builder::__private::Setter};
+    let sub_builder_trait = quote! {::robma_builder::__private::SubBuilder};
+
+    let mut impls = Vec::new();
+    let mut setters = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        if field.start_fn {
+            continue;
+        }
+        let name = field.name;
+        let setter_name = &field.setter_name;
+        let deprecated = &field.deprecated;
+        let index = proc_macro2::Literal::usize_unsuffixed(index);
+        let member = quote! {[(); #index]};
+        let (result, outer_result, error_generics) = match field.set_once {
+            Some(SetOnce::Strict) => (
+                quote! {std::result::Result<(), #error_name>},
+                quote! {std::result::Result<(), E>},
+                quote! {E},
+            ),
+            _ => (quote! {()}, quote! {()}, quote! {}),
+        };
+        let SetterResult {
+            return_type,
+            result: outer_ok,
+            forward,
+            ..
+        } = derive_setter_result(field, &quote! {E});
+        let ok = if forward.is_empty() {
+            quote! {}
+        } else {
+            quote! {std::result::Result::Ok(())}
+        };
+
+        if let Some(repeated_name) = &field.repeated_name {
+            let repeated_type =
+                get_angle_bracket_arg(field.field_type).expect("Expected vector type");
+            let docs =
+                derive_field_docs(field, &format!("Appends an element to `{}`.", name.unraw()));
+            let SetterArgument {
+                generics,
+                argument_type,
+                ..
+            } = derive_setter_argument(repeated_type, field.wrap, quote! {#repeated_type});
+            let bound = quote! {
+                where
+                    $builder: #setter_trait<#member, V, ()>,
+            };
+            let aliases = derive_alias_functions(
+                field,
+                &quote! {$vis},
+                repeated_name,
+                &quote! {<V>},
+                &quote! {x: V},
+                &bound,
+                &quote! {E},
+            );
+            impls.push(quote! {
+                impl #generics #setter_trait<#member, #argument_type, ()> for #builder_name {
+                    fn set(&mut self, x: #argument_type) {
+                        #[allow(deprecated)]
+                        self.#repeated_name(x);
+                    }
+                }
+            });
+            setters.push(quote! {
+                #docs
+                #(#deprecated)*
+                $vis fn #repeated_name<V>(&mut self, x: V) -> &mut Self
+                #bound
+                {
+                    #setter_trait::<#member, V, ()>::set(&mut self.$field, x);
+                    self
+                }
+
+                #aliases
+            });
+        } else if let Some(sub_builder) = &field.sub_builder {
+            let mut_name = format_ident!("{}_mut", name.unraw());
+            let mut_doc = format!("Returns the builder of `{}`.", name.unraw());
+            impls.push(quote! {
+                impl #sub_builder_trait<#member, #sub_builder> for #builder_name {
+                    fn sub_builder(&mut self) -> &mut #sub_builder {
+                        &mut self.#name
+                    }
+                }
+            });
+            if !field.flatten {
+                let docs = derive_field_docs(
+                    field,
+                    &format!("Sets the fields of `{}` with its builder.", name.unraw()),
+                );
+                setters.push(quote! {
+                    #docs
+                    #(#deprecated)*
+                    $vis fn #setter_name<F, B>(&mut self, f: F) -> &mut Self
+                    where
+                        F: std::ops::FnOnce(&mut B) -> &mut B,
+                        $builder: #sub_builder_trait<#member, B>,
+                    {
+                        f(#sub_builder_trait::<#member, B>::sub_builder(&mut self.$field));
+                        self
+                    }
+                });
+            }
+            setters.push(quote! {
+                #[doc = #mut_doc]
+                #(#deprecated)*
+                $vis fn #mut_name<B>(&mut self) -> &mut B
+                where
+                    $builder: #sub_builder_trait<#member, B>,
+                {
+                    #sub_builder_trait::<#member, B>::sub_builder(&mut self.$field)
+                }
+            });
+        } else if field.flag {
+            let if_name = format_ident!("{}_if", setter_name.unraw());
+            let docs = derive_field_docs(field, &format!("Sets `{}` to `true`.", name.unraw()));
+            let if_doc = format!("Sets `{}` to `condition`.", name.unraw());
+            let generics = if error_generics.is_empty() {
+                quote! {}
+            } else {
+                quote! {<#error_generics>}
+            };
+            let bound = quote! {
+                where
+                    $builder: #setter_trait<#member, bool, #outer_result>,
+            };
+            let aliases = derive_alias_functions(
+                field,
+                &quote! {$vis},
+                setter_name,
+                &generics,
+                &quote! {},
+                &bound,
+                &quote! {E},
+            );
+            impls.push(quote! {
+                impl #setter_trait<#member, bool, #result> for #builder_name {
+                    fn set(&mut self, x: bool) -> #result {
+                        #[allow(deprecated)]
+                        self.#if_name(x)#forward;
+                        #ok
+                    }
+                }
+            });
+            setters.push(quote! {
+                #docs
+                #(#deprecated)*
+                $vis fn #setter_name #generics(&mut self) -> #return_type
+                #bound
+                {
+                    #setter_trait::<#member, bool, #outer_result>::set(&mut self.$field, true)#forward;
+                    #outer_ok
+                }
+
+                #[doc = #if_doc]
+                #(#deprecated)*
+                $vis fn #if_name #generics(&mut self, condition: bool) -> #return_type
+                #bound
+                {
+                    #setter_trait::<#member, bool, #outer_result>::set(&mut self.$field, condition)#forward;
+                    #outer_ok
+                }
+
+                #aliases
+            });
+        } else {
+            let field_type = get_setter_type(field);
+            let docs = derive_field_docs(field, &format!("Sets `{}`.", name.unraw()));
+            let SetterArgument {
+                generics,
+                argument_type,
+                ..
+            } = derive_setter_argument(field_type, field.wrap, quote! {#field_type});
+            let outer_generics = if error_generics.is_empty() {
+                quote! {<V>}
+            } else {
+                quote! {<V, #error_generics>}
+            };
+            let bound = quote! {
+                where
+                    $builder: #setter_trait<#member, V, #outer_result>,
+            };
+            let aliases = derive_alias_functions(
+                field,
+                &quote! {$vis},
+                setter_name,
+                &outer_generics,
+                &quote! {x: V},
+                &bound,
+                &quote! {E},
+            );
+            impls.push(quote! {
+                impl #generics #setter_trait<#member, #argument_type, #result> for #builder_name {
+                    fn set(&mut self, x: #argument_type) -> #result {
+                        #[allow(deprecated)]
+                        self.#setter_name(x)#forward;
+                        #ok
+                    }
+                }
+            });
+            setters.push(quote! {
+                #docs
+                #(#deprecated)*
+                $vis fn #setter_name #outer_generics(&mut self, x: V) -> #return_type
+                #bound
+                {
+                    #setter_trait::<#member, V, #outer_result>::set(&mut self.$field, x)#forward;
+                    #outer_ok
+                }
+
+                #aliases
+            });
+        }
+    }
+
+    quote! {
+        #(#impls)*
+
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {
+            ($vis:vis [$builder:ty] $outer:ident $field:ident) => {
+                impl $outer {
+                    #(#setters)*
+                }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #macro_name as #name;
+    }
+}
+
+/// Invokes the macros of `derive_flatten_macro` for all flattened fields, through the path of the
+/// field type.
+fn derive_flatten_invocations(
+    builder_name: &syn::Ident,
+    fields: &[Field],
+) -> proc_macro2::TokenStream {
+    let invocations = fields.iter().filter(|field| field.flatten).map(|field| {
+        let field_name = field.name;
+        let vis = &field.vis;
+        let sub_builder = &field.sub_builder;
+        let path = match field.field_type {
+            syn::Type::Path(t) => &t.path,
+            _ => unreachable!("flattened fields are checked to be paths"),
+        };
+        // A struct without `#[builder(flattenable)]` has no macro, the error points at the type.
+        let span = syn::spanned::Spanned::span(field.field_type);
+        quote_spanned! {span=>
+            #path!(#vis [#sub_builder] #builder_name #field_name);
+        }
+    });
+
+    quote! {
+        #(#invocations)*
+    }
+}
+
+/// Formats a type like it is usually written, e.g. `Vec<String>` instead of `Vec < String >`.
+fn type_to_string(t: &syn::Type) -> String {
+    [
+        (" :: ", "::"),
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (" ;", ";"),
+        ("& ", "&"),
+        (" (", "("),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+    ]
+    .iter()
+    .fold(quote!(#t).to_string(), |result, (from, to)| {
+        result.replace(from, to)
+    })
+}
@@ -0,0 +1,314 @@
+//! The traits and types used by the code that [robma_builder](https://docs.rs/robma_builder) generates.
+//!
+//! Use them through the re-exports of `robma_builder`.
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// A type with a builder.
+///
+/// `#[derive(Builder)]` implements it if the builder has no start arguments, unless `#[builder(vis = "...")]`
+/// makes the builder less visible than the struct.
+pub trait Buildable {
+    /// The builder of the type.
+    type Builder: Default;
+
+    /// Creates a builder with all fields unset.
+    fn builder() -> Self::Builder;
+}
+
+/// A builder that creates a value.
+///
+/// `#[derive(Builder)]` implements it for the builder unless `build_fn(skip)` is given.
+pub trait Builder {
+    /// The type that the builder creates.
+    type Target;
+    /// The error of `build()`.
+    type Error;
+
+    /// Creates the value, fails if the fields of the builder are not valid.
+    fn build(&mut self) -> Result<Self::Target, Self::Error>;
+}
+
+/// A required field is not set when `build()` is called.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UninitializedFieldError {
+    field: Cow<'static, str>,
+}
+
+impl UninitializedFieldError {
+    /// Creates the error for the field with the name `field`.
+    pub fn new(field: &'static str) -> Self {
+        UninitializedFieldError {
+            field: Cow::Borrowed(field),
+        }
+    }
+
+    /// Returns the name of the field, or its path if it is a field of a sub-builder, e.g. `server.port`.
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Prefixes the path of the field with `member`, the member that stores the builder of the field.
+    pub fn in_member(self, member: &str) -> Self {
+        UninitializedFieldError {
+            field: Cow::Owned(format!("{}.{}", member, self.field)),
+        }
+    }
+}
+
+impl fmt::Display for UninitializedFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: not initialized", self.field)
+    }
+}
+
+impl std::error::Error for UninitializedFieldError {}
+
+/// The errors of the functions of a builder, except for a missing field which is an [`UninitializedFieldError`].
+///
+/// `#[derive(Builder)]` names it `<Builder>Error` with a type alias, e.g. `CommandBuilderError`.
+#[derive(Debug)]
+pub enum BuilderError {
+    /// An environment variable could not be read or parsed.
+    Env {
+        /// The name of the environment variable.
+        variable: &'static str,
+        /// The name of the field.
+        field: &'static str,
+        /// The reason of the error.
+        message: String,
+    },
+    /// There is no field with this name.
+    UnknownField {
+        /// The unknown name.
+        field: String,
+    },
+    /// A value could not be parsed.
+    Parse {
+        /// The name of the field.
+        field: &'static str,
+        /// The value that could not be parsed.
+        value: String,
+        /// The reason of the error.
+        message: String,
+    },
+    /// A command line argument is not known.
+    UnknownArgument {
+        /// The unknown argument.
+        argument: String,
+    },
+    /// A command line argument is missing its value.
+    MissingValue {
+        /// The argument without a value.
+        argument: String,
+    },
+    /// The help was requested on the command line.
+    Help {
+        /// The help text.
+        text: String,
+    },
+    /// Two fields that conflict with each other are set.
+    Conflict {
+        /// The name of the field with `conflicts_with`.
+        field: &'static str,
+        /// The name of the conflicting field.
+        other: &'static str,
+    },
+    /// A field is set without a field that it requires.
+    Requires {
+        /// The name of the field with `requires`.
+        field: &'static str,
+        /// The name of the required field.
+        required: &'static str,
+    },
+    /// More than one field of a group that allows at most one is set.
+    GroupConflict {
+        /// The name of the group.
+        group: &'static str,
+        /// The names of the fields that are set.
+        fields: Vec<&'static str>,
+    },
+    /// No field of a group that requires one is set.
+    GroupEmpty {
+        /// The name of the group.
+        group: &'static str,
+        /// The names of the fields of the group.
+        fields: &'static [&'static str],
+    },
+    /// A repeated field has fewer elements than its `min`.
+    TooFew {
+        /// The name of the field.
+        field: &'static str,
+        /// The minimal number of elements.
+        min: usize,
+        /// The number of elements.
+        count: usize,
+    },
+    /// A repeated field has more elements than its `max`.
+    TooMany {
+        /// The name of the field.
+        field: &'static str,
+        /// The maximal number of elements.
+        max: usize,
+        /// The number of elements.
+        count: usize,
+    },
+    /// A repeated field with `unique` contains an element twice.
+    Duplicate {
+        /// The name of the field.
+        field: &'static str,
+        /// The index of the repeated element.
+        index: usize,
+    },
+    /// A field with `set_once` is set a second time.
+    Overwritten {
+        /// The name of the field.
+        field: &'static str,
+    },
+    /// The builder of a field failed.
+    Nested {
+        /// The name of the field.
+        field: &'static str,
+        /// The error of the builder of the field.
+        source: Box<dyn std::error::Error>,
+    },
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuilderError::Env {
+                variable,
+                field,
+                message,
+            } => write!(
+                f,
+                "failed to read environment variable `{}` for field `{}`: {}",
+                variable, field, message
+            ),
+            BuilderError::UnknownField { field } => write!(f, "unknown field `{}`", field),
+            BuilderError::Parse {
+                field,
+                value,
+                message,
+            } => write!(
+                f,
+                "failed to parse `{}` for field `{}`: {}",
+                value, field, message
+            ),
+            BuilderError::UnknownArgument { argument } => {
+                write!(f, "unknown argument `{}`, see `--help`", argument)
+            }
+            BuilderError::MissingValue { argument } => {
+                write!(f, "missing value for argument `{}`", argument)
+            }
+            BuilderError::Help { text } => write!(f, "{}", text),
+            BuilderError::Conflict { field, other } => {
+                write!(f, "`{}` conflicts with `{}`", field, other)
+            }
+            BuilderError::Requires { field, required } => {
+                write!(f, "`{}` requires `{}` to be set", field, required)
+            }
+            BuilderError::GroupConflict { group, fields } => write!(
+                f,
+                "at most one field of group `{}` may be set, but `{}` are set",
+                group,
+                fields.join("`, `")
+            ),
+            BuilderError::GroupEmpty { group, fields } => write!(
+                f,
+                "one of the fields `{}` of group `{}` has to be set",
+                fields.join("`, `"),
+                group
+            ),
+            BuilderError::TooFew { field, min, count } => write!(
+                f,
+                "the number of elements of `{}` must be at least {}, but is {}",
+                field, min, count
+            ),
+            BuilderError::TooMany { field, max, count } => write!(
+                f,
+                "the number of elements of `{}` must be at most {}, but is {}",
+                field, max, count
+            ),
+            BuilderError::Duplicate { field, index } => write!(
+                f,
+                "`{}` contains the element at index {} twice",
+                field, index
+            ),
+            BuilderError::Overwritten { field } => write!(f, "`{}` is set more than once", field),
+            BuilderError::Nested { field, source } => {
+                write!(f, "in field `{}`: {}", field, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuilderError {}
+
+/// Describes a field of a builder, the constant `FIELDS` of a builder describes all of its fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldInfo {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The type of the field as written in the struct.
+    pub ty: &'static str,
+    /// Whether the field is required, optional or repeated.
+    pub kind: FieldKind,
+    /// The name of the setter of a single element of a repeated field.
+    pub each: Option<&'static str>,
+    /// The doc comment of the field.
+    pub doc: &'static str,
+}
+
+/// Whether a field is required, optional or repeated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// The field has to be set.
+    Required,
+    /// The field is an `Option` and defaults to `None`.
+    Optional,
+    /// The field is set element by element and defaults to no elements.
+    Repeated,
+}
+
+/// Used by the code that `#[derive(Builder)]` generates, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    /// A member of a builder whose setter takes a `T` and returns `R`, for the setters that a
+    /// flattening builder forwards. `I` is the index of the member, written as `[(); index]`.
+    pub trait Setter<I, T, R> {
+        /// Calls the setter.
+        fn set(&mut self, x: T) -> R;
+    }
+
+    /// A member of a builder that stores the builder `B`, `I` is the index of the member.
+    pub trait SubBuilder<I, B> {
+        /// Returns the stored builder.
+        fn sub_builder(&mut self) -> &mut B;
+    }
+
+    /// Formats the help text of `parse_args()`, the options are aligned in a column.
+    pub fn help_text(header: &str, options: &[(&str, &str)]) -> String {
+        let width = options
+            .iter()
+            .map(|(option, _)| option.len())
+            .max()
+            .unwrap_or(0);
+        let mut help = header.to_owned();
+        for (option, description) in options {
+            let line = format!("  {:width$}  {}", option, description, width = width);
+            help.push_str(line.trim_end());
+            help.push('\n');
+        }
+        help
+    }
+
+    /// A builder that `build!` can set, `Fields` has a function for each member of the builder
+    /// that calls its setter.
+    pub trait Literal {
+        /// The type with the functions of the members.
+        type Fields;
+    }
+}
@@ -0,0 +1,55 @@
+// Configurations are often assembled in layers: defaults, a config file,
+// environment variables and finally command line flags.
+//
+// The generated `merge` method overrides every field that is set in the other
+// builder, `or_else` only fills the fields that are still unset. Repeated
+// fields are appended by default and replaced with
+// #[builder(merge = "replace")].
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(each = "env", merge = "replace")]
+    env: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let mut defaults = Command::builder();
+    defaults
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .env("RUST_LOG=info".to_owned())
+        .current_dir(".".to_owned());
+
+    let mut file = Command::builder();
+    file.executable("cross".to_owned())
+        .arg("--release".to_owned())
+        .env("RUST_LOG=debug".to_owned())
+        .env("RUST_BACKTRACE=1".to_owned());
+
+    let command = defaults.merge(file).build().unwrap();
+    assert_eq!(command.executable, "cross");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.env, vec!["RUST_LOG=debug", "RUST_BACKTRACE=1"]);
+    assert_eq!(command.current_dir.as_deref(), Some("."));
+
+    let mut cli = Command::builder();
+    cli.arg("--offline".to_owned());
+
+    let mut defaults = Command::builder();
+    defaults
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .env("RUST_LOG=info".to_owned());
+
+    let command = cli.or_else(defaults).build().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["--offline"]);
+    assert_eq!(command.env, vec!["RUST_LOG=info"]);
+    assert!(command.current_dir.is_none());
+}
@@ -0,0 +1,13 @@
+// The merge strategy only applies to repeated fields, so using it without
+// `each` is reported as an error.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(merge = "append")]
+    args: Vec<String>,
+}
+
+fn main() {}
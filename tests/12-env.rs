@@ -0,0 +1,82 @@
+// Services are often configured through environment variables.
+//
+// The field attribute #[builder(env = "...")] names the variable a field is
+// read from, the struct attribute #[builder(env_prefix = "...")] reads every
+// field from the prefix followed by the upper case field name. Values are
+// parsed with FromStr, repeated fields are split at a separator.
+//
+// `from_env` creates a builder from the environment, `apply_env` layers the
+// environment on top of an existing builder. Parse failures are reported with
+// the name of the variable and the field. A sub-builder marked with
+// #[builder(sub_builder, env)] reads the variables of its inner struct.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+#[builder(env_prefix = "TEST12_")]
+pub struct Server {
+    host: String,
+    #[builder(env = "TEST12_LISTEN_PORT")]
+    port: u16,
+    #[builder(each = "path", env_separator = ":")]
+    paths: Vec<String>,
+    #[builder(each = "retry_delay")]
+    retry_delays: Vec<u64>,
+    timeout: Option<u32>,
+}
+
+#[derive(Builder)]
+pub struct Service {
+    name: String,
+    #[builder(sub_builder, env)]
+    server: Server,
+}
+
+fn main() {
+    std::env::set_var("TEST12_HOST", "localhost");
+    std::env::set_var("TEST12_LISTEN_PORT", "8080");
+    std::env::set_var("TEST12_PATHS", "/usr/bin:/bin");
+    std::env::set_var("TEST12_RETRY_DELAYS", "10,100,1000");
+
+    let server = ServerBuilder::from_env().unwrap().build().unwrap();
+    assert_eq!(server.host, "localhost");
+    assert_eq!(server.port, 8080);
+    assert_eq!(server.paths, vec!["/usr/bin", "/bin"]);
+    assert_eq!(server.retry_delays, vec![10, 100, 1000]);
+    assert_eq!(server.timeout, None);
+
+    std::env::remove_var("TEST12_HOST");
+    std::env::set_var("TEST12_TIMEOUT", "30");
+    let server = Server::builder()
+        .host("example.com".to_owned())
+        .apply_env()
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(server.host, "example.com");
+    assert_eq!(server.timeout, Some(30));
+
+    std::env::set_var("TEST12_LISTEN_PORT", "eighty");
+    let error = ServerBuilder::from_env().unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("TEST12_LISTEN_PORT"), "{}", message);
+    assert!(message.contains("`port`"), "{}", message);
+    assert!(matches!(
+        error,
+        ServerBuilderError::Env {
+            variable: "TEST12_LISTEN_PORT",
+            field: "port",
+            ..
+        }
+    ));
+
+    std::env::set_var("TEST12_LISTEN_PORT", "8081");
+    let service = ServiceBuilder::from_env()
+        .unwrap()
+        .name("api".to_owned())
+        .server(|s| s.host("localhost".to_owned()))
+        .build()
+        .unwrap();
+    assert_eq!(service.name, "api");
+    assert_eq!(service.server.port, 8081);
+}
@@ -0,0 +1,59 @@
+// Config editors and REPLs need to set fields by name at runtime.
+//
+// With the struct attribute #[builder(set_by_name)], `set_by_name` parses the value with FromStr and stores it in the named
+// field, repeated fields get the value appended. `from_pairs` builds a new
+// builder from key/value pairs. Unknown fields and unparsable values are
+// reported as distinct error variants.
+//
+// The values are parsed with FromStr, so this is opt-in: not every field type
+// implements it.
+
+use robma_builder::Builder;
+use std::collections::BTreeMap;
+
+#[derive(Builder)]
+#[builder(set_by_name)]
+pub struct Server {
+    host: String,
+    port: u16,
+    #[builder(each = "retry_delay")]
+    retry_delays: Vec<u64>,
+    timeout: Option<u32>,
+}
+
+fn main() {
+    let server = Server::builder()
+        .set_by_name("host", "localhost")
+        .unwrap()
+        .set_by_name("port", "8080")
+        .unwrap()
+        .set_by_name("retry_delays", "10")
+        .unwrap()
+        .set_by_name("retry_delays", "100")
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(server.host, "localhost");
+    assert_eq!(server.port, 8080);
+    assert_eq!(server.retry_delays, vec![10, 100]);
+    assert_eq!(server.timeout, None);
+
+    let mut config = BTreeMap::new();
+    config.insert("host".to_owned(), "example.com".to_owned());
+    config.insert("port".to_owned(), "443".to_owned());
+    config.insert("timeout".to_owned(), "30".to_owned());
+    let server = ServerBuilder::from_pairs(config).unwrap().build().unwrap();
+    assert_eq!(server.host, "example.com");
+    assert_eq!(server.port, 443);
+    assert_eq!(server.timeout, Some(30));
+
+    let error = Server::builder().set_by_name("hostname", "x").unwrap_err();
+    assert!(matches!(error, ServerBuilderError::UnknownField { ref field } if field == "hostname"));
+    assert_eq!(error.to_string(), "unknown field `hostname`");
+
+    let error = ServerBuilder::from_pairs(vec![("port", "eighty")]).unwrap_err();
+    assert!(matches!(
+        error,
+        ServerBuilderError::Parse { field: "port", ref value, .. } if value == "eighty"
+    ));
+}
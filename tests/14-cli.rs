@@ -0,0 +1,83 @@
+// Small tools should not need to duplicate every field into a hand-written
+// argument parser.
+//
+// The struct attribute #[builder(cli)] generates `parse_args`, which maps
+// `--current-dir <value>` and `--current-dir=<value>` to the current_dir
+// field. Repeated flags named after `each` feed the repeated fields and bool
+// fields become presence flags. `--help` returns an error that displays a
+// help text generated from the doc comments.
+
+use robma_builder::Builder;
+
+/// Runs a command.
+#[derive(Builder)]
+#[builder(cli)]
+pub struct Command {
+    /// The program to run.
+    executable: String,
+    /// An argument passed to the program.
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    /// The working directory.
+    current_dir: Option<String>,
+    /// Print the command before running it.
+    verbose: bool,
+    retries: u8,
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+fn main() {
+    let command = CommandBuilder::parse_args(args(&[
+        "--executable",
+        "cargo",
+        "--arg",
+        "build",
+        "--arg=--release",
+        "--current-dir=..",
+        "--retries",
+        "3",
+    ]))
+    .unwrap()
+    .build()
+    .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+    assert!(!command.verbose);
+    assert_eq!(command.retries, 3);
+
+    let command = CommandBuilder::parse_args(args(&["--verbose", "--executable=ls", "--retries=0"]))
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(command.verbose);
+    assert!(command.args.is_empty());
+
+    let error = CommandBuilder::parse_args(args(&["--exe", "ls"])).unwrap_err();
+    assert!(matches!(error, CommandBuilderError::UnknownArgument { ref argument } if argument == "--exe"));
+
+    let error = CommandBuilder::parse_args(args(&["--executable"])).unwrap_err();
+    assert!(matches!(error, CommandBuilderError::MissingValue { ref argument } if argument == "--executable"));
+
+    let error = CommandBuilder::parse_args(args(&["--retries", "many"])).unwrap_err();
+    assert!(matches!(error, CommandBuilderError::Parse { field: "retries", .. }));
+
+    let error = CommandBuilder::parse_args(args(&["--help"])).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "\
+Runs a command.
+
+Options:
+      --executable <EXECUTABLE>    The program to run. (required)
+      --arg <ARG>...               An argument passed to the program.
+      --current-dir <CURRENT_DIR>  The working directory.
+      --verbose                    Print the command before running it.
+      --retries <RETRIES>          (required)
+  -h, --help                       Print this help
+"
+    );
+}
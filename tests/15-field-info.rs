@@ -0,0 +1,59 @@
+// User interfaces and diagnostics want to show which parts of a partially
+// built struct are still missing before calling `build`.
+//
+// The builder gets a constant FIELDS of `robma_builder::FieldInfo` describing
+// every field: its name, its type as written, whether it is required, optional
+// or repeated, its `each` name and its doc comment. `missing_fields` returns
+// the names of the required fields that are still unset and `is_<field>_set`
+// checks a single field.
+
+use robma_builder::{Builder, FieldKind};
+
+#[derive(Builder)]
+pub struct Command {
+    /// The program to run.
+    executable: String,
+    /// The arguments.
+    ///
+    /// One element per call to `arg`.
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+    r#type: String,
+}
+
+fn main() {
+    assert_eq!(
+        CommandBuilder::FIELDS
+            .iter()
+            .map(|field| field.name)
+            .collect::<Vec<_>>(),
+        vec!["executable", "args", "current_dir", "type"]
+    );
+
+    let args = CommandBuilder::FIELDS[1];
+    assert_eq!(args.ty, "Vec<String>");
+    assert_eq!(args.kind, FieldKind::Repeated);
+    assert_eq!(args.each, Some("arg"));
+    assert_eq!(args.doc, "The arguments.\n\nOne element per call to `arg`.");
+
+    let current_dir = CommandBuilder::FIELDS[2];
+    assert_eq!(current_dir.ty, "Option<String>");
+    assert_eq!(current_dir.kind, FieldKind::Optional);
+    assert_eq!(current_dir.each, None);
+    assert_eq!(current_dir.doc, "");
+
+    assert_eq!(CommandBuilder::FIELDS[0].kind, FieldKind::Required);
+
+    let mut builder = Command::builder();
+    assert_eq!(builder.missing_fields(), vec!["executable", "type"]);
+    assert!(!builder.is_executable_set());
+    assert!(!builder.is_args_set());
+
+    builder.executable("cargo".to_owned()).arg("build".to_owned());
+    assert_eq!(builder.missing_fields(), vec!["type"]);
+    assert!(builder.is_executable_set());
+    assert!(builder.is_args_set());
+    assert!(!builder.is_current_dir_set());
+    assert!(!builder.is_type_set());
+}
@@ -0,0 +1,57 @@
+// Code that receives a builder may want to check what has been configured and
+// undo values.
+//
+// Generate `get_<field>` returning an Option of a reference, or a slice for
+// repeated fields, `clear_<field>` to unset a single field and `reset` to
+// unset all of them.
+//
+// The struct attribute #[builder(vis = "...")] sets the visibility of the
+// builder and all its methods, the same attribute on a field overrides the
+// visibility of the setters, getters and `clear_` method of that field.
+
+mod command {
+    use robma_builder::Builder;
+
+    #[derive(Builder)]
+    #[builder(vis = "pub")]
+    pub struct Command {
+        pub executable: String,
+        #[builder(each = "arg")]
+        pub args: Vec<String>,
+        #[builder(vis = "pub(crate)")]
+        pub current_dir: Option<String>,
+    }
+}
+
+use command::Command;
+
+fn main() {
+    let mut builder = Command::builder();
+    assert_eq!(builder.get_executable(), None);
+    assert!(builder.get_args().is_empty());
+
+    builder
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .arg("--release".to_owned())
+        .current_dir("..".to_owned());
+    assert_eq!(builder.get_executable().unwrap(), "cargo");
+    assert_eq!(builder.get_args(), ["build", "--release"]);
+    assert_eq!(builder.get_current_dir().unwrap(), "..");
+
+    builder.clear_current_dir().clear_args();
+    assert_eq!(builder.get_current_dir(), None);
+    assert!(builder.get_args().is_empty());
+    assert_eq!(builder.get_executable().unwrap(), "cargo");
+
+    builder.arg("test".to_owned());
+    let command = builder.build().unwrap();
+    assert_eq!(command.args, vec!["test"]);
+    assert!(command.current_dir.is_none());
+
+    builder.executable("cargo".to_owned()).arg("build".to_owned());
+    builder.reset();
+    assert_eq!(builder.get_executable(), None);
+    assert!(builder.get_args().is_empty());
+    assert!(builder.build().is_err());
+}
@@ -0,0 +1,19 @@
+// Fields can restrict the visibility of their setters, getters and `clear_`
+// method below the visibility of the builder.
+
+mod command {
+    use robma_builder::Builder;
+
+    #[derive(Builder)]
+    #[builder(vis = "pub")]
+    pub struct Command {
+        pub executable: String,
+        #[builder(vis = "pub(self)")]
+        pub secret: String,
+    }
+}
+
+fn main() {
+    let builder = command::Command::builder();
+    builder.get_secret();
+}
@@ -0,0 +1,91 @@
+// The generated names may collide with existing types and methods.
+//
+// #[builder(name = "...")] renames the builder type,
+// #[builder(builder_fn(name = "..."))] the function creating the builder and
+// #[builder(build_fn(name = "..."))] the function creating the struct.
+// `builder_fn(skip)` and `build_fn(skip)` omit the function so that it can be
+// written by hand using the storage of the builder.
+// #[builder(setter(name = "..."))] renames the setter of a single field, e.g.
+// when the field name collides with a method of the builder.
+
+use robma_builder::Builder;
+
+pub struct CommandBuilder;
+
+#[derive(Builder)]
+#[builder(
+    name = "CommandOptions",
+    builder_fn(name = "options"),
+    build_fn(name = "finish")
+)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+impl Command {
+    pub fn builder() -> CommandBuilder {
+        CommandBuilder
+    }
+}
+
+#[derive(Builder)]
+#[builder(builder_fn(skip), build_fn(skip))]
+pub struct Server {
+    host: String,
+    port: Option<u16>,
+}
+
+impl Server {
+    fn builder(host: &str) -> ServerBuilder {
+        ServerBuilder {
+            host: Some(host.to_owned()),
+            port: None,
+        }
+    }
+}
+
+impl ServerBuilder {
+    fn build(&mut self) -> Server {
+        Server {
+            host: self.host.take().unwrap(),
+            port: Some(self.port.unwrap_or(80)),
+        }
+    }
+}
+
+#[derive(Builder)]
+pub struct Update {
+    #[builder(setter(name = "merge_with"))]
+    merge: Option<bool>,
+    #[builder(flag, setter(name = "do_reset"))]
+    reset: bool,
+}
+
+fn main() {
+    let _: CommandBuilder = Command::builder();
+
+    let command = Command::options()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .finish()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+
+    let _: Option<CommandOptionsError> = None;
+
+    let server = Server::builder("localhost").build();
+    assert_eq!(server.host, "localhost");
+    assert_eq!(server.port, Some(80));
+
+    let update = Update::builder()
+        .merge_with(true)
+        .do_reset_if(true)
+        .build()
+        .unwrap();
+    assert_eq!(update.merge, Some(true));
+    assert!(update.reset);
+    assert!(Update::builder().do_reset().build().unwrap().reset);
+}
@@ -0,0 +1,37 @@
+// Generated items carry documentation, so that crates with
+// #![deny(missing_docs)] can expose a builder.
+//
+// The doc comments of each field are copied onto its setters and getters, and
+// the builder lists its required and optional fields.
+//
+// A #[deprecated] attribute on a field is forwarded to its setters, so that
+// callers get a deprecation warning while the derive itself stays silent.
+
+#![deny(missing_docs, warnings)]
+//! Documented builders.
+
+use robma_builder::Builder;
+
+/// A command to run.
+#[derive(Builder)]
+#[builder(vis = "pub", cli)]
+pub struct Command {
+    /// The program to run.
+    pub executable: String,
+    /// The arguments of the program.
+    #[builder(each = "arg")]
+    pub args: Vec<String>,
+    /// The working directory.
+    pub current_dir: Option<String>,
+    /// Use `current_dir` instead.
+    #[deprecated(note = "use `current_dir` instead")]
+    pub cwd: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+}
@@ -0,0 +1,16 @@
+// Calling the setter of a #[deprecated] field produces a deprecation warning.
+
+#![deny(deprecated)]
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    current_dir: Option<String>,
+    #[deprecated(note = "use `current_dir` instead")]
+    cwd: Option<String>,
+}
+
+fn main() {
+    Command::builder().cwd("..".to_owned());
+}
@@ -0,0 +1,41 @@
+// Fields may only exist for some configurations, e.g. behind
+// #[cfg(feature = "tls")].
+//
+// The compiler evaluates #[cfg] and #[cfg_attr] on the fields before the
+// derive macro sees the struct: disabled fields get no storage, setter or
+// build assignment, enabled fields behave like any other field and builder
+// attributes inside of #[cfg_attr] are applied.
+//
+// `cfg(all())` is always enabled and `cfg(any())` is always disabled, which
+// stands in for a feature that is on or off.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[cfg(all())]
+    tls: bool,
+    #[cfg(any())]
+    proxy: TypeThatDoesNotExist,
+    #[cfg_attr(all(), builder(each = "arg"))]
+    args: Vec<String>,
+    #[cfg_attr(any(), builder(each = "env"))]
+    env: Vec<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .tls(true)
+        .arg("build".to_owned())
+        .env(vec!["RUST_LOG=debug".to_owned()])
+        .build()
+        .unwrap();
+    assert!(command.tls);
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.env, vec!["RUST_LOG=debug"]);
+
+    let names: Vec<&str> = CommandBuilder::FIELDS.iter().map(|field| field.name).collect();
+    assert_eq!(names, vec!["executable", "tls", "args", "env"]);
+}
@@ -0,0 +1,14 @@
+// A field that is disabled by #[cfg] has no setter on the builder.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[cfg(any())]
+    tls: bool,
+}
+
+fn main() {
+    Command::builder().tls(true);
+}
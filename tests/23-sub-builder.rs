@@ -0,0 +1,97 @@
+// Structs that contain other structs with a builder should not need the inner
+// struct to be built separately.
+//
+// A field marked with #[builder(sub_builder)] stores the builder of its type.
+// It is configured with a closure, `.server(|s| s.port(80))`, or through
+// `server_mut()`. `build()` builds the inner struct, a missing field of it is
+// still an `UninitializedFieldError` whose field is prefixed with the name of
+// the outer field. `set_by_name` forwards paths like `server.port` to the
+// inner builder. The inner struct may rename its `builder()` and `build()`
+// functions.
+
+use robma_builder::{Builder, UninitializedFieldError};
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(set_by_name)]
+pub struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(set_by_name)]
+pub struct Config {
+    name: String,
+    #[builder(sub_builder)]
+    server: Server,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(builder_fn(name = "options"), build_fn(name = "finish"))]
+pub struct Limits {
+    retries: u32,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct Job {
+    #[builder(sub_builder)]
+    limits: Limits,
+}
+
+fn main() {
+    let config = Config::builder()
+        .name("app".to_owned())
+        .server(|s| s.host("localhost".to_owned()).port(80))
+        .build()
+        .unwrap();
+    assert_eq!(
+        config.server,
+        Server {
+            host: "localhost".to_owned(),
+            port: 80,
+        }
+    );
+
+    let mut builder = Config::builder();
+    builder.name("app".to_owned());
+    builder.server_mut().host("localhost".to_owned());
+    assert_eq!(builder.missing_fields(), vec!["server"]);
+    assert!(!builder.is_server_set());
+
+    builder.set_by_name("server.port", "8080").unwrap();
+    assert_eq!(builder.get_server().get_port(), Some(&8080));
+    assert!(builder.missing_fields().is_empty());
+    assert_eq!(builder.build().unwrap().server.port, 8080);
+
+    let error = Config::builder()
+        .name("app".to_owned())
+        .server(|s| s.host("localhost".to_owned()))
+        .build()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "server.port: not initialized");
+    let error = error.downcast_ref::<UninitializedFieldError>().unwrap();
+    assert_eq!(error.field(), "server.port");
+
+    let mut builder = Config::builder();
+    match builder.set_by_name("server.hostname", "localhost") {
+        Err(ConfigBuilderError::Nested { field: "server", .. }) => {}
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+    match builder.set_by_name("server", "localhost") {
+        Err(ConfigBuilderError::UnknownField { .. }) => {}
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+
+    let mut defaults = Config::builder();
+    defaults.server(|s| s.host("localhost".to_owned()).port(80));
+    let mut overrides = Config::builder();
+    overrides.name("app".to_owned()).server(|s| s.port(8080));
+    let config = defaults.merge(overrides).build().unwrap();
+    assert_eq!(config.server.host, "localhost");
+    assert_eq!(config.server.port, 8080);
+
+    let job = Job::builder().limits(|l| l.retries(3)).build().unwrap();
+    assert_eq!(job.limits, Limits::options().retries(3).finish().unwrap());
+    let error = Job::builder().build().unwrap_err();
+    assert_eq!(error.to_string(), "limits.retries: not initialized");
+}
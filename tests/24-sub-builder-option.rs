@@ -0,0 +1,17 @@
+// Sub-builders always build the inner struct, so they are not supported on
+// optional fields.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Server {
+    port: u16,
+}
+
+#[derive(Builder)]
+pub struct Config {
+    #[builder(sub_builder)]
+    server: Option<Server>,
+}
+
+fn main() {}
@@ -0,0 +1,121 @@
+// Options that are shared between many structs should not add a level of
+// nesting to the builders of all of them.
+//
+// A field marked with #[builder(flatten)] stores the builder of its type like
+// a sub-builder, but the setters of the inner builder are generated directly
+// on the outer builder. `build()` assembles the inner struct, and `set_by_name`
+// falls back to the fields of the flattened builders. With #[builder(cli)]
+// `parse_args` forwards unknown flags to the flattened builders and the help
+// text lists their options, the inner struct needs #[builder(cli)] as well.
+// The inner struct is
+// marked with #[builder(flattenable)], so that only the structs that are
+// flattened generate the code to forward their setters.
+//
+// The inner struct may live in another module, the types of its setters are
+// resolved in that module.
+
+use robma_builder::Builder;
+
+mod opts {
+    use robma_builder::Builder;
+
+    type Seconds = u64;
+
+    #[derive(Builder, Debug, PartialEq)]
+    #[builder(cli, flattenable)]
+    pub struct CommonOpts {
+        /// Seconds until the request is aborted.
+        pub timeout: Seconds,
+        #[builder(each = "header")]
+        pub headers: Vec<String>,
+        pub verbose: Option<bool>,
+    }
+}
+
+/// Runs a command.
+#[derive(Builder, Debug, PartialEq)]
+#[builder(cli)]
+pub struct Command {
+    /// The program to run.
+    executable: String,
+    #[builder(flatten)]
+    common: opts::CommonOpts,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct Upload {
+    file: String,
+    #[builder(flatten)]
+    common: opts::CommonOpts,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .timeout(5)
+        .header("a".to_owned())
+        .header("b".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.common.timeout, 5);
+    assert_eq!(command.common.headers, vec!["a", "b"]);
+    assert_eq!(command.common.verbose, None);
+
+    let mut builder = Upload::builder();
+    builder.file("data.bin".to_owned()).verbose(true);
+    assert_eq!(builder.missing_fields(), vec!["timeout"]);
+    builder.common_mut().timeout(1);
+    let upload = builder.build().unwrap();
+    assert_eq!(upload.common.verbose, Some(true));
+
+    let error = Upload::builder()
+        .file("data.bin".to_owned())
+        .build()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "timeout: not initialized");
+
+    let mut builder = Command::builder();
+    builder.set_by_name("headers", "c").unwrap();
+    assert_eq!(builder.get_common().get_headers(), ["c"]);
+    match builder.set_by_name("verbose", "maybe") {
+        Err(CommandBuilderError::Nested { field: "common", .. }) => {}
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+    match builder.set_by_name("timeouts", "5") {
+        Err(CommandBuilderError::UnknownField { .. }) => {}
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+
+    let args = ["--executable", "cargo", "--timeout", "3", "--header=a", "--verbose"];
+    let command = CommandBuilder::parse_args(args.iter().map(|arg| arg.to_string()))
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(command.common.timeout, 3);
+    assert_eq!(command.common.headers, vec!["a"]);
+    assert_eq!(command.common.verbose, Some(true));
+
+    let error = CommandBuilder::parse_args(vec!["--timeout".to_owned()]).unwrap_err();
+    match error {
+        CommandBuilderError::Nested { field: "common", .. } => {}
+        other => panic!("unexpected error: {:?}", other),
+    }
+    let error = CommandBuilder::parse_args(vec!["--timeouts=3".to_owned()]).unwrap_err();
+    assert!(matches!(error, CommandBuilderError::UnknownArgument { ref argument } if argument == "--timeouts=3"));
+
+    let error = CommandBuilder::parse_args(vec!["--help".to_owned()]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "\
+Runs a command.
+
+Options:
+      --executable <EXECUTABLE>  The program to run. (required)
+      --timeout <TIMEOUT>        Seconds until the request is aborted. (required)
+      --header <HEADER>...
+      --verbose
+  -h, --help                     Print this help
+"
+    );
+}
@@ -0,0 +1,19 @@
+// The setters of a flattened builder share the namespace of the outer builder,
+// a field of the outer struct with the same name as an inner one is an error.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+#[builder(flattenable)]
+pub struct CommonOpts {
+    timeout: u64,
+}
+
+#[derive(Builder)]
+pub struct Command {
+    timeout: u64,
+    #[builder(flatten)]
+    common: CommonOpts,
+}
+
+fn main() {}
@@ -0,0 +1,88 @@
+// Functions with many parameters are easier to call with named arguments.
+//
+// The attribute #[builder] on a function replaces it with a function that
+// returns a builder of its arguments, `call()` calls the original function.
+// The arguments are classified like the fields of a struct: `Option` arguments
+// may be omitted and `each` appends single elements.
+//
+// On an impl block, #[builder] generates a builder for every associated
+// function marked with #[builder]. The builder of `new` is created by
+// `builder()` and finished by `build()`.
+
+#[robma_builder::builder]
+fn connect(
+    host: String,
+    port: Option<u16>,
+    #[builder(each = "header")] headers: Vec<String>,
+) -> String {
+    format!("{}:{} {}", host, port.unwrap_or(80), headers.join(","))
+}
+
+/// Logs a message.
+#[robma_builder::builder(set_by_name)]
+pub fn log(mut message: String, level: Option<u8>) {
+    message.push('!');
+    assert_eq!(level, None);
+    assert_eq!(message, "hello!");
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Connection {
+    host: String,
+    port: u16,
+    parent: Option<Box<Connection>>,
+}
+
+#[robma_builder::builder]
+impl Connection {
+    #[builder]
+    pub fn new(host: String, port: Option<u16>, parent: Option<Box<Self>>) -> Self {
+        Connection {
+            host,
+            port: port.unwrap_or(80),
+            parent,
+        }
+    }
+
+    #[builder]
+    fn local_host(port: u16) -> Self {
+        Self::helper(port)
+    }
+
+    fn helper(port: u16) -> Self {
+        Connection {
+            host: "localhost".to_owned(),
+            port,
+            parent: None,
+        }
+    }
+}
+
+fn main() {
+    let connection = connect()
+        .host("localhost".to_owned())
+        .header("a".to_owned())
+        .header("b".to_owned())
+        .call()
+        .unwrap();
+    assert_eq!(connection, "localhost:80 a,b");
+
+    let _: ConnectBuilder = connect();
+    assert!(connect().call().is_err());
+
+    LogBuilder::from_pairs(vec![("message", "hello")])
+        .unwrap()
+        .call()
+        .unwrap();
+
+    let parent = Connection::local_host().port(8080).call().unwrap();
+    let connection: Connection = Connection::builder()
+        .host("example.com".to_owned())
+        .parent(Box::new(parent))
+        .build()
+        .unwrap();
+    assert_eq!(connection.port, 80);
+    assert_eq!(connection.parent.unwrap().host, "localhost");
+
+    let _: ConnectionLocalHostBuilder = Connection::local_host();
+}
@@ -0,0 +1,16 @@
+// The builder is a separate value, so it cannot call a method that borrows
+// `self`.
+
+pub struct Connection {
+    host: String,
+}
+
+#[robma_builder::builder]
+impl Connection {
+    #[builder]
+    fn reconnect(&mut self, host: String) {
+        self.host = host;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,98 @@
+// Fields that always have to be set can be passed to `builder()` directly.
+//
+// Fields marked with #[builder(start_fn)] become the arguments of
+// `builder()`, in the order of the fields. They have no setter, `build()`
+// takes them like the other fields, so they do not have to implement `Clone`
+// and are missing again after `build()` until `merge()` sets them.
+// The other functions that create a builder take them as their first
+// arguments as well, and `reset()` keeps them.
+
+use robma_builder::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(set_by_name)]
+pub struct Command {
+    #[builder(start_fn)]
+    executable: String,
+    #[builder(start_fn)]
+    timeout: Option<u64>,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(env = "TEST29_CURRENT_DIR")]
+    current_dir: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Connection {
+    port: u16,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct Request {
+    #[builder(start_fn)]
+    connection: Connection,
+    path: String,
+}
+
+#[robma_builder::builder]
+fn run(#[builder(start_fn)] executable: &'static str, verbose: Option<bool>) -> String {
+    format!("{} {}", executable, verbose.unwrap_or(false))
+}
+
+fn main() {
+    let mut builder = Command::builder("cargo".to_owned(), None);
+    assert_eq!(builder.get_executable().map(String::as_str), Some("cargo"));
+    assert_eq!(builder.missing_fields(), vec!["current_dir"]);
+
+    let command = builder
+        .arg("build".to_owned())
+        .current_dir("..".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(
+        command,
+        Command {
+            executable: "cargo".to_owned(),
+            timeout: None,
+            args: vec!["build".to_owned()],
+            current_dir: "..".to_owned(),
+        }
+    );
+
+    builder.arg("test".to_owned()).reset();
+    assert_eq!(builder.get_executable(), None);
+    assert!(builder.get_args().is_empty());
+    assert_eq!(builder.missing_fields(), vec!["executable", "timeout", "current_dir"]);
+
+    let other = CommandBuilder::from_pairs(
+        "rustc".to_owned(),
+        Some(5),
+        vec![("current_dir", "/"), ("args", "--version")],
+    )
+    .unwrap();
+    let command = builder.merge(other).build().unwrap();
+    assert_eq!(command.executable, "rustc");
+    assert_eq!(command.timeout, Some(5));
+    assert_eq!(command.args, vec!["--version"]);
+
+    let builder = CommandBuilder::from_env("make".to_owned(), None).unwrap();
+    assert_eq!(builder.get_executable().map(String::as_str), Some("make"));
+
+    assert!(Command::builder("cargo".to_owned(), None)
+        .set_by_name("executable", "make")
+        .is_err());
+
+    let mut builder = Request::builder(Connection { port: 80 });
+    let request = builder.path("/".to_owned()).build().unwrap();
+    assert_eq!(request.connection, Connection { port: 80 });
+    let error = builder.path("/".to_owned()).build().unwrap_err();
+    assert_eq!(error.to_string(), "connection: not initialized");
+    assert_eq!(builder.missing_fields(), vec!["connection"]);
+    let request = builder
+        .merge(Request::builder(Connection { port: 81 }))
+        .build()
+        .unwrap();
+    assert_eq!(request.connection, Connection { port: 81 });
+
+    assert_eq!(run("ls").verbose(true).call().unwrap(), "ls true");
+}
@@ -0,0 +1,12 @@
+// Repeated fields are filled with single elements, they cannot be passed to
+// `builder()`.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(start_fn, each = "arg")]
+    args: Vec<String>,
+}
+
+fn main() {}
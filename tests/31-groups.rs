@@ -0,0 +1,86 @@
+// Some fields are alternatives to each other, or only make sense together.
+//
+// Fields are put into groups with #[builder(group = "...")], the groups are
+// declared on the struct with `groups(name(exactly_one))`, `at_most_one` or
+// `at_least_one`. #[builder(requires = "...")] and
+// #[builder(conflicts_with = "...")] relate two fields. `build()` checks the
+// rules before it takes any field and reports the fields in the error.
+
+use robma_builder::Builder;
+
+#[derive(Builder, Debug)]
+#[builder(groups(stdin(exactly_one), auth(at_most_one)))]
+pub struct Command {
+    executable: String,
+    #[builder(group = "stdin")]
+    stdin_file: Option<String>,
+    #[builder(group = "stdin")]
+    stdin_bytes: Option<Vec<u8>>,
+    #[builder(group = "auth")]
+    token: Option<String>,
+    #[builder(group = "auth", requires = "user")]
+    password: Option<String>,
+    user: Option<String>,
+    #[builder(each = "arg", conflicts_with = "stdin_bytes")]
+    args: Vec<String>,
+}
+
+fn error(builder: &mut CommandBuilder) -> CommandBuilderError {
+    *builder
+        .build()
+        .unwrap_err()
+        .downcast::<CommandBuilderError>()
+        .unwrap()
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder.executable("cat".to_owned());
+    match error(&mut builder) {
+        CommandBuilderError::GroupEmpty {
+            group: "stdin",
+            fields,
+        } => assert_eq!(fields, ["stdin_file", "stdin_bytes"]),
+        other => panic!("unexpected error: {}", other),
+    }
+
+    builder
+        .stdin_file("input.txt".to_owned())
+        .stdin_bytes(vec![1, 2, 3]);
+    let error_message = error(&mut builder).to_string();
+    assert_eq!(
+        error_message,
+        "at most one field of group `stdin` may be set, but `stdin_file`, `stdin_bytes` are set"
+    );
+
+    builder.clear_stdin_file().arg("-n".to_owned());
+    match error(&mut builder) {
+        CommandBuilderError::Conflict {
+            field: "args",
+            other: "stdin_bytes",
+        } => {}
+        other => panic!("unexpected error: {}", other),
+    }
+
+    builder.clear_args().password("secret".to_owned());
+    match error(&mut builder) {
+        CommandBuilderError::Requires {
+            field: "password",
+            required: "user",
+        } => {}
+        other => panic!("unexpected error: {}", other),
+    }
+
+    builder.user("root".to_owned()).token("token".to_owned());
+    match error(&mut builder) {
+        CommandBuilderError::GroupConflict { group: "auth", fields } => {
+            assert_eq!(fields, vec!["token", "password"])
+        }
+        other => panic!("unexpected error: {}", other),
+    }
+
+    builder.clear_token();
+    let command = builder.build().unwrap();
+    assert_eq!(command.stdin_bytes, Some(vec![1, 2, 3]));
+    assert_eq!(command.password.as_deref(), Some("secret"));
+}
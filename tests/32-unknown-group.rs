@@ -0,0 +1,14 @@
+// Groups are declared on the struct, so that their rule is written once.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+#[builder(groups(stdin(exactly_one)))]
+pub struct Command {
+    #[builder(group = "stdin")]
+    stdin_file: Option<String>,
+    #[builder(group = "stdout")]
+    stdout_file: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,119 @@
+// Setters of pointer types can wrap their argument.
+//
+// With #[builder(setter(wrap))] the setter of a `Box`, `Arc`, `Rc` or `Cow`
+// member accepts anything that converts into it, e.g. the plain value or a
+// `&str`. When the pointer holds a trait object, the setter takes any type
+// implementing the trait and boxes it, so closures can be passed directly. For
+// repeated members the single-element setter wraps its argument. The setter
+// of a `Cow<T>` takes anything that converts into the owned type of `T`, also
+// for types that are not in `std`.
+//
+// Builders with trait object members cannot be compared, their `Debug` output
+// shows the type of these members instead of their value. The setters of
+// flattened builders wrap their arguments as well.
+
+use robma_builder::Builder;
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub struct Event {
+    pub code: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Store {
+    pub path: String,
+}
+
+#[derive(Builder)]
+pub struct Server {
+    #[builder(setter(wrap))]
+    handler: Box<dyn Fn(&Event) -> u32 + Send>,
+    #[builder(setter(wrap))]
+    store: Arc<Store>,
+    #[builder(setter(wrap))]
+    cache: Option<Rc<Store>>,
+    #[builder(setter(wrap))]
+    name: Cow<'static, str>,
+    #[builder(setter(wrap), each = "hook")]
+    hooks: Vec<Arc<dyn Fn() -> u32 + Send + Sync>>,
+}
+
+mod widgets {
+    use robma_builder::Builder;
+    use std::sync::Arc;
+
+    #[derive(Builder)]
+    #[builder(flattenable)]
+    pub struct Labels {
+        #[builder(setter(wrap))]
+        pub label: Option<Arc<str>>,
+    }
+}
+
+#[derive(Builder)]
+pub struct Window {
+    title: String,
+    #[builder(setter(wrap))]
+    store: Cow<'static, Store>,
+    #[builder(flatten)]
+    labels: widgets::Labels,
+}
+
+fn main() {
+    let offset = 1;
+    let server = Server::builder()
+        .handler(move |event: &Event| event.code + offset)
+        .store(Store {
+            path: "/tmp".to_owned(),
+        })
+        .cache(Rc::new(Store {
+            path: "/cache".to_owned(),
+        }))
+        .name("local")
+        .hook(|| 1)
+        .hook(|| 2)
+        .build()
+        .unwrap();
+
+    assert_eq!((server.handler)(&Event { code: 41 }), 42);
+    assert_eq!(server.store.path, "/tmp");
+    assert_eq!(server.cache.unwrap().path, "/cache");
+    assert_eq!(server.name, "local");
+    assert_eq!(
+        server.hooks.iter().map(|hook| hook()).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+
+    let server = Server::builder()
+        .handler(|_: &Event| 0)
+        .store(Arc::new(Store {
+            path: "/".to_owned(),
+        }))
+        .name(format!("server-{}", 1))
+        .build()
+        .unwrap();
+    assert_eq!(server.name, "server-1");
+    assert!(server.cache.is_none());
+    assert!(server.hooks.is_empty());
+
+    let window = Window::builder()
+        .title("main".to_owned())
+        .store(Store {
+            path: "/window".to_owned(),
+        })
+        .label("ok")
+        .build()
+        .unwrap();
+    assert_eq!(&*window.labels.label.unwrap(), "ok");
+    assert_eq!(window.store.path, "/window");
+
+    let mut builder = Server::builder();
+    builder.name("debug").hook(|| 3);
+    assert_eq!(
+        format!("{:?}", builder),
+        "ServerBuilder { handler: <unset>, store: <unset>, cache: <unset>, name: \"debug\", \
+         hooks: [\"Arc < dyn Fn() -> u32 + Send + Sync >\"] }"
+    );
+}
@@ -0,0 +1,11 @@
+// Only pointer types can wrap the argument of their setter.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Server {
+    #[builder(setter(wrap))]
+    name: String,
+}
+
+fn main() {}
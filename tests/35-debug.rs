@@ -0,0 +1,63 @@
+// Builders are printed while debugging configurations, which must not leak
+// passwords or tokens into logs.
+//
+// The `Debug` output of a builder prints unset members as `<unset>`, so that
+// an unset optional member can be told apart from a required one that is set.
+// Optional members that are set print as `Some(...)`. Members marked with
+// #[builder(sensitive)] print as `"<redacted>"` once set, and their values are
+// left out of the errors of `set_by_name()`.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+#[builder(set_by_name)]
+pub struct Connection {
+    host: String,
+    port: Option<u16>,
+    user: Option<String>,
+    #[builder(sensitive)]
+    password: String,
+    #[builder(sensitive)]
+    pin: Option<u32>,
+    #[builder(each = "option")]
+    options: Vec<String>,
+    #[builder(sensitive, each = "token")]
+    tokens: Vec<String>,
+}
+
+fn main() {
+    let mut builder = Connection::builder();
+    assert_eq!(
+        format!("{:?}", builder),
+        "ConnectionBuilder { host: <unset>, port: <unset>, user: <unset>, password: <unset>, \
+         pin: <unset>, options: [], tokens: [] }"
+    );
+
+    builder
+        .host("localhost".to_owned())
+        .port(5432)
+        .password("hunter2".to_owned())
+        .option("ssl".to_owned())
+        .token("secret".to_owned());
+    let output = format!("{:?}", builder);
+    assert_eq!(
+        output,
+        "ConnectionBuilder { host: \"localhost\", port: Some(5432), user: <unset>, \
+         password: \"<redacted>\", pin: <unset>, options: [\"ssl\"], tokens: \"<redacted>\" }"
+    );
+    assert!(!output.contains("hunter2"));
+    assert!(!output.contains("secret"));
+
+    let error = builder.set_by_name("pin", "12x4").unwrap_err();
+    assert!(!error.to_string().contains("12x4"));
+    assert_eq!(
+        error.to_string(),
+        "failed to parse `<redacted>` for field `pin`: invalid digit found in string"
+    );
+
+    let error = builder.set_by_name("port", "54x2").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "failed to parse `54x2` for field `port`: invalid digit found in string"
+    );
+}
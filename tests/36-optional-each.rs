@@ -0,0 +1,62 @@
+// A repeated field that was never configured is not the same as one that was
+// configured to be empty.
+//
+// Fields of type `Option<Vec<T>>` with #[builder(each = "...")] stay `None`
+// until the first element is appended, then they hold `Some(vec)`. An
+// environment variable that is present but empty configures an empty `Vec`.
+// `merge()` appends or replaces the elements like for other repeated fields.
+
+use robma_builder::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(env_prefix = "TEST36_")]
+pub struct Deployment {
+    name: String,
+    #[builder(each = "tag")]
+    tags: Option<Vec<String>>,
+    #[builder(each = "region", merge = "replace")]
+    regions: Option<Vec<String>>,
+}
+
+fn main() {
+    let deployment = Deployment::builder()
+        .name("web".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(deployment.tags, None);
+    assert_eq!(deployment.regions, None);
+
+    let mut builder = Deployment::builder();
+    builder
+        .name("web".to_owned())
+        .tag("blue".to_owned())
+        .tag("canary".to_owned());
+    assert_eq!(builder.get_tags(), ["blue", "canary"]);
+    assert_eq!(builder.get_regions(), [] as [String; 0]);
+    let deployment = builder.build().unwrap();
+    assert_eq!(
+        deployment.tags,
+        Some(vec!["blue".to_owned(), "canary".to_owned()])
+    );
+
+    let mut other = Deployment::builder();
+    other.tag("green".to_owned()).region("eu".to_owned());
+    let mut builder = Deployment::builder();
+    builder
+        .name("api".to_owned())
+        .region("us".to_owned())
+        .merge(other);
+    let deployment = builder.build().unwrap();
+    assert_eq!(deployment.tags, Some(vec!["green".to_owned()]));
+    assert_eq!(deployment.regions, Some(vec!["eu".to_owned()]));
+
+    let mut builder = Deployment::builder();
+    builder.tag("blue".to_owned()).clear_tags();
+    assert_eq!(builder.get_tags(), [] as [String; 0]);
+
+    std::env::set_var("TEST36_NAME", "worker");
+    std::env::set_var("TEST36_TAGS", "");
+    let deployment = DeploymentBuilder::from_env().unwrap().build().unwrap();
+    assert_eq!(deployment.tags, Some(vec![]));
+    assert_eq!(deployment.regions, None);
+}
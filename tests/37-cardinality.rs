@@ -0,0 +1,72 @@
+// Some repeated fields need at least one element, or must not contain the
+// same element twice.
+//
+// #[builder(each = "...", min = N, max = M)] makes `build()` check the number
+// of elements of a repeated field, #[builder(each = "...", unique)] checks
+// that no element appears twice, which requires the elements to implement
+// `Eq` and `Hash`. The error names the field. Optional repeated fields are
+// only checked once they are set.
+
+use robma_builder::Builder;
+
+#[derive(Builder, Debug)]
+pub struct Job {
+    #[builder(each = "step", min = 1, max = 3)]
+    steps: Vec<String>,
+    #[builder(each = "label", unique)]
+    labels: Vec<String>,
+    #[builder(each = "retry", min = 2)]
+    retries: Option<Vec<u32>>,
+}
+
+fn main() {
+    let error = Job::builder().build().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "the number of elements of `steps` must be at least 1, but is 0"
+    );
+
+    let error = Job::builder()
+        .step("checkout".to_owned())
+        .step("build".to_owned())
+        .step("test".to_owned())
+        .step("deploy".to_owned())
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "the number of elements of `steps` must be at most 3, but is 4"
+    );
+
+    let error = Job::builder()
+        .step("build".to_owned())
+        .label("linux".to_owned())
+        .label("x86".to_owned())
+        .label("linux".to_owned())
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`labels` contains the element at index 2 twice"
+    );
+
+    let error = Job::builder()
+        .step("build".to_owned())
+        .retry(10)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "the number of elements of `retries` must be at least 2, but is 1"
+    );
+
+    let job = Job::builder()
+        .step("build".to_owned())
+        .label("linux".to_owned())
+        .label("x86".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(job.steps, vec!["build"]);
+    assert_eq!(job.labels, vec!["linux", "x86"]);
+    assert_eq!(job.retries, None);
+}
@@ -0,0 +1,11 @@
+// The number of elements can only be checked for repeated fields.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Job {
+    #[builder(min = 1)]
+    steps: Vec<String>,
+}
+
+fn main() {}
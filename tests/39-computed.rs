@@ -0,0 +1,57 @@
+// Values derived from other fields should be consistent by construction.
+//
+// A field marked with #[builder(computed = "...")] has no setter, `build()`
+// evaluates the expression after all other fields are resolved. The other
+// fields are available as variables of the same name, computed fields may
+// use the computed fields declared before them. With
+// #[builder(computed(with = "..."))] the function is called with references
+// to the other fields, in the order of their declaration.
+
+use robma_builder::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(computed = "format!(\"{} {}\", executable, args.join(\" \"))")]
+    full_cmd: String,
+    #[builder(computed = "full_cmd.len()")]
+    length: usize,
+}
+
+fn describe(host: &String, port: &Option<u16>) -> String {
+    format!("{}:{}", host, port.unwrap_or(80))
+}
+
+#[derive(Builder, Debug)]
+pub struct Server {
+    host: String,
+    port: Option<u16>,
+    #[builder(computed(with = "describe"))]
+    address: String,
+}
+
+#[robma_builder::builder]
+fn connect(host: &'static str, #[builder(computed = "host.len() > 3")] long: bool) -> String {
+    format!("{} {}", host, long)
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .arg("--release".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.full_cmd, "cargo build --release");
+    assert_eq!(command.length, 21);
+    assert_eq!(command.args, vec!["build", "--release"]);
+
+    let server = Server::builder().host("localhost".to_owned()).build().unwrap();
+    assert_eq!(server.address, "localhost:80");
+
+    assert_eq!(connect().host("example").call().unwrap(), "example true");
+
+    assert_eq!(CommandBuilder::FIELDS.len(), 2);
+}
@@ -0,0 +1,12 @@
+// Computed fields have no setter, so no other attributes apply to them.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(computed = "vec![executable.clone()]", each = "arg")]
+    args: Vec<String>,
+}
+
+fn main() {}
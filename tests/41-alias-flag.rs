@@ -0,0 +1,72 @@
+// Setters are renamed during migrations, and boolean options read better
+// without an argument.
+//
+// #[builder(alias = "...")] generates another setter that forwards to the
+// setter of the field, it may be given more than once. #[builder(flag)] on a
+// `bool` or `Option<bool>` field generates a setter without arguments that
+// sets `true`, and a setter ending in `_if` that takes the value. A `bool`
+// flag that is never set is `false`.
+
+use robma_builder::Builder;
+
+mod opts {
+    use robma_builder::Builder;
+
+    #[derive(Builder, Debug, PartialEq)]
+    #[builder(flattenable)]
+    pub struct Output {
+        #[builder(flag, alias = "q")]
+        pub quiet: bool,
+    }
+}
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct Command {
+    executable: String,
+    #[builder(alias = "cwd", alias = "working_dir")]
+    current_dir: Option<String>,
+    #[builder(each = "arg", alias = "argument")]
+    args: Vec<String>,
+    #[builder(flag)]
+    verbose: bool,
+    #[builder(flag)]
+    color: Option<bool>,
+    #[builder(flatten)]
+    output: opts::Output,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .cwd("..".to_owned())
+        .arg("build".to_owned())
+        .argument("--release".to_owned())
+        .verbose()
+        .color_if(false)
+        .q()
+        .build()
+        .unwrap();
+    assert_eq!(
+        command,
+        Command {
+            executable: "cargo".to_owned(),
+            current_dir: Some("..".to_owned()),
+            args: vec!["build".to_owned(), "--release".to_owned()],
+            verbose: true,
+            color: Some(false),
+            output: opts::Output { quiet: true },
+        }
+    );
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .working_dir("/".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.current_dir, Some("/".to_owned()));
+    assert!(!command.verbose);
+    assert_eq!(command.color, None);
+    assert!(!command.output.quiet);
+
+    assert!(Command::builder().missing_fields() == vec!["executable"]);
+}
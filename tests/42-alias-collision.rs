@@ -0,0 +1,12 @@
+// An alias cannot take the name of another setter.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(alias = "executable")]
+    program: Option<String>,
+}
+
+fn main() {}
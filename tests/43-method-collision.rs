@@ -0,0 +1,28 @@
+// The methods generated for the fields must not collide with each other or
+// with the other methods of the builder.
+//
+// All generated names are checked before the builder is generated, the
+// errors point at the field or attribute that introduces the second name.
+// A field named `builder` is fine, `builder()` is a function of the struct.
+// The builder and `builder()` are still generated, so that code using them
+// only shows the collisions.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    builder: String,
+    build: String,
+    #[builder(each = "env")]
+    args: Vec<String>,
+    env: Option<String>,
+    #[builder(each = "path")]
+    paths: Vec<String>,
+    #[builder(each = "path")]
+    include_paths: Vec<String>,
+    merge: Option<bool>,
+}
+
+fn main() {
+    let _ = Command::builder();
+}
@@ -0,0 +1,127 @@
+// Configurations assembled from many sources can set a field twice by
+// accident, and the second value silently wins.
+//
+// #[builder(set_once)] on a field or on the struct records a second set, and
+// build() fails with an error that names the field. With
+// #[builder(set_once(strict))] the setter itself returns a `Result`. merge()
+// records fields that are set on both sides, even strict ones, and reset()
+// forgets everything. A boolean command line flag that is given twice counts as
+// setting it twice.
+
+use robma_builder::Builder;
+
+mod opts {
+    use robma_builder::Builder;
+
+    #[derive(Builder, Debug, PartialEq)]
+    #[builder(set_by_name, flattenable)]
+    pub struct Output {
+        #[builder(flag, set_once(strict), alias = "q")]
+        pub quiet: bool,
+    }
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(set_by_name)]
+pub struct Command {
+    #[builder(set_once)]
+    executable: String,
+    #[builder(set_once(strict), alias = "cwd")]
+    current_dir: Option<String>,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    timeout: Option<u32>,
+    #[builder(flatten)]
+    output: opts::Output,
+}
+
+#[derive(Builder, Debug)]
+#[builder(set_once)]
+pub struct Server {
+    host: String,
+    #[builder(set_once(strict))]
+    port: u16,
+    #[builder(each = "tag")]
+    tags: Vec<String>,
+}
+
+#[derive(Builder, Debug)]
+#[builder(cli)]
+pub struct Job {
+    #[builder(set_once)]
+    verbose: bool,
+    #[builder(set_once(strict))]
+    quiet: bool,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder
+        .executable("cargo".to_owned())
+        .executable("rustc".to_owned())
+        .timeout(1)
+        .timeout(2);
+    let error = builder.build().unwrap_err();
+    assert_eq!(error.to_string(), "`executable` is set more than once");
+
+    builder.reset();
+    builder.executable("cargo".to_owned());
+    builder.cwd("..".to_owned()).unwrap();
+    assert!(matches!(
+        builder.current_dir("/".to_owned()),
+        Err(CommandBuilderError::Overwritten { field: "current_dir" })
+    ));
+    builder.q().unwrap();
+    assert!(builder.quiet_if(false).is_err());
+    assert!(builder.set_by_name("current_dir", "/").is_err());
+    assert_eq!(builder.get_current_dir(), Some(&"..".to_owned()));
+    let command = builder.arg("build".to_owned()).arg("--release".to_owned()).build().unwrap();
+    assert_eq!(command.current_dir, Some("..".to_owned()));
+    assert_eq!(command.output, opts::Output { quiet: true });
+
+    let mut builder = Command::builder();
+    builder.set_by_name("executable", "cargo").unwrap();
+    builder.set_by_name("executable", "rustc").unwrap();
+    assert!(builder.build().is_err());
+
+    let mut builder = Command::builder();
+    builder.executable("cargo".to_owned());
+    let mut other = Command::builder();
+    other.current_dir("/".to_owned()).unwrap();
+    builder.merge(other);
+    assert_eq!(builder.get_current_dir(), Some(&"/".to_owned()));
+    let mut other = Command::builder();
+    other.executable("rustc".to_owned());
+    builder.merge(other);
+    let error = builder.build().unwrap_err();
+    assert_eq!(error.to_string(), "`executable` is set more than once");
+
+    let mut builder = Server::builder();
+    builder
+        .host("localhost".to_owned())
+        .tag("a".to_owned())
+        .tag("b".to_owned())
+        .port(80)
+        .unwrap();
+    assert!(builder.port(8080).is_err());
+    builder.host("example.com".to_owned());
+    let error = builder.build().unwrap_err();
+    assert_eq!(error.to_string(), "`host` is set more than once");
+
+    let args = vec!["--verbose".to_owned(), "--verbose".to_owned()];
+    let error = JobBuilder::parse_args(args).unwrap().build().unwrap_err();
+    assert_eq!(error.to_string(), "`verbose` is set more than once");
+
+    let args = vec!["--quiet".to_owned(), "--quiet=true".to_owned()];
+    assert!(matches!(
+        JobBuilder::parse_args(args),
+        Err(JobBuilderError::Overwritten { field: "quiet" })
+    ));
+    let args = vec!["--quiet".to_owned(), "--quiet".to_owned()];
+    assert!(JobBuilder::parse_args(args).is_err());
+    let job = JobBuilder::parse_args(vec!["--quiet".to_owned()])
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(job.quiet && !job.verbose);
+}
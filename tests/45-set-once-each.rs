@@ -0,0 +1,13 @@
+// Repeated fields are meant to be set many times, so set_once does not apply
+// to them.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg", set_once)]
+    args: Vec<String>,
+}
+
+fn main() {}
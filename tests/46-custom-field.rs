@@ -0,0 +1,88 @@
+// The builder does not have to store a field in the type of the target. Raw
+// user input can be kept as a string and parsed at the end, or elements can be
+// collected into a `Vec` and turned into a set.
+//
+// #[builder(field(ty = "...", build = "..."))] stores the field as `ty`,
+// starting from its default value, and the setter takes a `ty`. build()
+// evaluates the `build` expression with the builder as `self`, errors can be
+// returned with `?`. A field counts as set when it differs from the default.
+
+use robma_builder::Builder;
+use std::collections::BTreeSet;
+
+fn parse_port(port: &str) -> Result<u16, std::num::ParseIntError> {
+    if port.is_empty() {
+        Ok(80)
+    } else {
+        port.parse()
+    }
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(set_by_name)]
+pub struct Server {
+    host: String,
+    #[builder(field(ty = "String", build = "parse_port(&self.port)?"), alias = "listen")]
+    port: u16,
+    #[builder(
+        field(ty = "String", build = "Some(self.token.clone()).filter(|token| !token.is_empty())"),
+        sensitive,
+        requires = "host"
+    )]
+    token: Option<String>,
+}
+
+#[derive(Builder, Debug)]
+pub struct Job {
+    #[builder(field(ty = "Vec<String>", build = "self.tags.iter().cloned().collect()"))]
+    tags: BTreeSet<String>,
+}
+
+fn main() {
+    let server = Server::builder()
+        .host("localhost".to_owned())
+        .port("8080".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(
+        server,
+        Server {
+            host: "localhost".to_owned(),
+            port: 8080,
+            token: None,
+        }
+    );
+
+    let mut builder = Server::builder();
+    builder.host("localhost".to_owned()).listen("http".to_owned());
+    assert_eq!(builder.get_port(), "http");
+    let error = builder.build().unwrap_err();
+    assert_eq!(error.to_string(), "invalid digit found in string");
+
+    let mut builder = Server::builder();
+    builder.set_by_name("host", "localhost").unwrap();
+    builder.set_by_name("token", "secret").unwrap();
+    assert!(builder.is_token_set());
+    assert!(!builder.is_port_set());
+    assert_eq!(
+        format!("{:?}", builder),
+        r#"ServerBuilder { host: "localhost", port: "", token: "<redacted>" }"#
+    );
+    let mut other = Server::builder();
+    other.port("443".to_owned());
+    builder.merge(other);
+    builder.or_else(Server::builder());
+    let server = builder.build().unwrap();
+    assert_eq!(server.port, 443);
+    assert_eq!(server.token, Some("secret".to_owned()));
+
+    let job = Job::builder()
+        .tags(vec!["b".to_owned(), "a".to_owned(), "b".to_owned()])
+        .build()
+        .unwrap();
+    assert_eq!(job.tags.into_iter().collect::<Vec<_>>(), vec!["a", "b"]);
+
+    assert!(Server::builder().missing_fields() == vec!["host"]);
+    let error = Server::builder().token("secret".to_owned()).build().unwrap_err();
+    assert_eq!(error.to_string(), "`token` requires `host` to be set");
+}
@@ -0,0 +1,16 @@
+// A field with its own storage type has a single setter for that type, so it
+// cannot have an `each` setter as well.
+
+use robma_builder::Builder;
+use std::collections::BTreeSet;
+
+#[derive(Builder)]
+pub struct Server {
+    #[builder(
+        field(ty = "Vec<String>", build = "self.tags.iter().cloned().collect()"),
+        each = "tag"
+    )]
+    tags: BTreeSet<String>,
+}
+
+fn main() {}
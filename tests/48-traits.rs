@@ -0,0 +1,74 @@
+// Generic code needs to name the builder of a type and call build() on it.
+//
+// Every derive implements `robma_builder::Builder` for the builder, and
+// `robma_builder::Buildable` for the struct if the builder has no start
+// arguments and its visibility is not narrowed below the one of the struct,
+// which it follows by default. Builders without start arguments
+// implement `Default`. A missing required field is reported as an
+// `UninitializedFieldError`, the other errors of all builders are a
+// `BuilderError`.
+
+use robma_builder::{Buildable, Builder, BuilderError, UninitializedFieldError};
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(name = "ServerOptions", build_fn(name = "finish"))]
+struct Server {
+    host: String,
+    #[builder(set_once)]
+    port: Option<u16>,
+}
+
+fn load<T, F>(configure: F) -> Result<T, Box<dyn std::error::Error>>
+where
+    T: Buildable,
+    T::Builder: Builder<Target = T, Error = Box<dyn std::error::Error>>,
+    F: FnOnce(&mut T::Builder),
+{
+    let mut builder = T::builder();
+    configure(&mut builder);
+    builder.build()
+}
+
+fn finish<B: Builder>(builder: &mut B) -> Result<B::Target, B::Error> {
+    builder.build()
+}
+
+fn main() {
+    let command: Command = load(|builder: &mut CommandBuilder| {
+        builder.executable("cargo".to_owned()).arg("test".to_owned());
+    })
+    .unwrap();
+    assert_eq!(command.args, vec!["test".to_owned()]);
+
+    let error = load::<Command, _>(|_| {}).unwrap_err();
+    let error = error.downcast_ref::<UninitializedFieldError>().unwrap();
+    assert_eq!(error.field(), "executable");
+    assert_eq!(error.to_string(), "executable: not initialized");
+
+    let mut builder = ServerOptions::default();
+    builder.host("localhost".to_owned());
+    assert_eq!(
+        finish(&mut builder).unwrap(),
+        Server {
+            host: "localhost".to_owned(),
+            port: None,
+        }
+    );
+
+    builder.host("localhost".to_owned()).port(80).port(8080);
+    let error = load::<Server, _>(|options: &mut ServerOptions| {
+        *options = std::mem::take(&mut builder);
+    })
+    .unwrap_err();
+    match error.downcast_ref::<BuilderError>() {
+        Some(BuilderError::Overwritten { field: "port" }) => {}
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
@@ -0,0 +1,118 @@
+// Setting many members one by one is noisy when all values are known up front.
+//
+// build!(Command { ... }) accepts struct literal syntax and returns the result
+// of build(). Each value is passed to the setter of its member, converted with
+// `Into` unless the setter wraps it, and list literals append their elements
+// one by one with the `each` setter. A member followed by `?` is only set if
+// its value is `Some`. Shorthand `name` means `name: name`. The values may use
+// any local variable, also one named `builder`.
+
+use std::sync::Arc;
+
+use robma_builder::{build, Builder};
+
+mod config {
+    use robma_builder::Builder;
+
+    #[derive(Builder, Debug, PartialEq)]
+    #[builder(build_fn(name = "finish"))]
+    pub struct Server {
+        pub host: String,
+        #[builder(field(ty = "String", build = "self.port.parse()?"))]
+        pub port: u16,
+    }
+}
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    env: Vec<String>,
+    #[builder(each = "tag")]
+    tags: Option<Vec<String>>,
+    current_dir: Option<String>,
+    #[builder(flag)]
+    verbose: bool,
+    timeout: Option<u32>,
+}
+
+#[derive(Builder)]
+pub struct Server {
+    #[builder(setter(wrap))]
+    handler: Box<dyn Fn(u32) -> u32>,
+    #[builder(setter(wrap), each = "hook")]
+    hooks: Vec<Arc<dyn Fn() -> u32>>,
+    #[builder(set_once(strict))]
+    port: Option<u16>,
+    #[builder(setter(name = "merge_with"))]
+    merge: Option<bool>,
+}
+
+fn main() {
+    let current_dir = Some("..");
+    let timeout: Option<u32> = None;
+    let executable = "cargo";
+    let command = build!(Command {
+        executable,
+        args: ["build", "--release"],
+        env: vec!["RUST_LOG=debug".to_owned()],
+        current_dir?: current_dir,
+        timeout?: timeout,
+        verbose: true,
+    })
+    .unwrap();
+    assert_eq!(
+        command,
+        Command {
+            executable: "cargo".to_owned(),
+            args: vec!["build".to_owned(), "--release".to_owned()],
+            env: vec!["RUST_LOG=debug".to_owned()],
+            tags: None,
+            current_dir: Some("..".to_owned()),
+            verbose: true,
+            timeout: None,
+        }
+    );
+
+    let command = build!(Command {
+        executable: "rustc",
+        tags: ["a"],
+        env: [],
+    })
+    .unwrap();
+    assert_eq!(command.tags, Some(vec!["a".to_owned()]));
+    assert!(command.args.is_empty());
+    assert!(!command.verbose);
+
+    let builder = "cargo";
+    let command = build!(Command {
+        executable: builder,
+        env: [],
+    })
+    .unwrap();
+    assert_eq!(command.executable, "cargo");
+
+    let error = build!(Command {}).unwrap_err();
+    assert_eq!(error.to_string(), "executable: not initialized");
+
+    let server = build!(config::Server {
+        host: "localhost",
+        port: "8080",
+    })
+    .unwrap();
+    assert_eq!(server.port, 8080);
+    assert!(build!(config::Server { host: "localhost", port: "http" }).is_err());
+
+    let server = build!(Server {
+        handler: |x| x + 1,
+        hooks: [|| 1, || 2],
+        port: 8080u16,
+        merge: true,
+    })
+    .unwrap();
+    assert_eq!((server.handler)(1), 2);
+    assert_eq!(server.hooks.iter().map(|hook| hook()).sum::<u32>(), 3);
+    assert_eq!(server.port, Some(8080));
+    assert_eq!(server.merge, Some(true));
+}
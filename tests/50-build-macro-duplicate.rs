@@ -0,0 +1,17 @@
+// Like in a struct literal, every member may only be given once.
+
+use robma_builder::{build, Builder};
+
+#[derive(Builder, Debug)]
+pub struct Command {
+    executable: String,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let _ = build!(Command {
+        executable: "cargo",
+        current_dir: "..",
+        executable: "rustc",
+    });
+}
@@ -0,0 +1,72 @@
+// Flattened and sub-builder types are usually imported with `use` instead of
+// being written with their module path.
+//
+// The builder of such a field is found through the struct, also when it is
+// renamed on import, and the forwarded setters work for members of private
+// types, sub-builders and strict `set_once` members.
+
+use robma_builder::Builder;
+
+mod opts {
+    use robma_builder::Builder;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub(crate) struct Level(pub u8);
+
+    #[derive(Builder, Debug, PartialEq)]
+    pub struct Limits {
+        pub retries: u32,
+    }
+
+    #[derive(Builder)]
+    #[builder(flattenable)]
+    pub struct Common {
+        pub timeout: Option<u64>,
+        level: Option<Level>,
+        #[builder(flag, set_once(strict))]
+        pub quiet: bool,
+        #[builder(sub_builder)]
+        pub limits: Limits,
+    }
+
+    impl Common {
+        pub(crate) fn level(&self) -> Option<Level> {
+            self.level
+        }
+    }
+}
+
+use opts::Common;
+use opts::Limits as RetryLimits;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(flatten)]
+    common: Common,
+    #[builder(sub_builder)]
+    retry_limits: RetryLimits,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut builder = Command::builder();
+    builder
+        .executable("cargo".to_owned())
+        .timeout(5)
+        .level(opts::Level(2))
+        .limits(|limits| limits.retries(3))
+        .retry_limits_mut()
+        .retries(1);
+    builder.quiet()?;
+    assert!(builder.quiet_if(false).is_err());
+    builder.timeout(10).retry_limits_mut().retries(2);
+    let command = builder.build()?;
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.common.timeout, Some(10));
+    assert_eq!(command.common.level(), Some(opts::Level(2)));
+    assert!(command.common.quiet);
+    assert_eq!(command.common.limits, RetryLimits { retries: 3 });
+    assert_eq!(command.retry_limits, RetryLimits { retries: 2 });
+    Ok(())
+}
@@ -0,0 +1,33 @@
+// The builder stores the arguments of the function until `call()`, so it
+// cannot hold borrowed arguments or `impl Trait` arguments, and it cannot
+// leave out arguments that are removed by `#[cfg]`.
+//
+// `&'static` arguments and function types that elide their lifetimes are
+// fine, the others are rejected with an error on the argument.
+
+#[robma_builder::builder]
+fn greet(name: &str, times: Option<u32>) -> String {
+    name.repeat(times.unwrap_or(1) as usize)
+}
+
+#[robma_builder::builder]
+fn label(name: std::borrow::Cow<'_, str>) -> String {
+    name.into_owned()
+}
+
+#[robma_builder::builder]
+fn count(items: impl Iterator<Item = u32>) -> usize {
+    items.count()
+}
+
+#[robma_builder::builder]
+fn connect(host: String, #[cfg(feature = "tls")] certificate: Vec<u8>) -> String {
+    host
+}
+
+#[robma_builder::builder]
+fn apply(name: &'static str, transform: fn(&str) -> String) -> String {
+    transform(name)
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// Only structs marked with #[builder(flattenable)] can be flattened, the error
+// points at the type of the flattened field.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+pub struct CommonOpts {
+    timeout: u64,
+}
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(flatten)]
+    common: CommonOpts,
+}
+
+fn main() {}
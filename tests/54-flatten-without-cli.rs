@@ -0,0 +1,21 @@
+// Setting a flattened member by name or from the command line needs the same
+// support from the builder of the inner struct, the error points at the type
+// of the flattened field.
+
+use robma_builder::Builder;
+
+#[derive(Builder)]
+#[builder(flattenable)]
+pub struct CommonOpts {
+    timeout: u64,
+}
+
+#[derive(Builder)]
+#[builder(cli)]
+pub struct Command {
+    executable: String,
+    #[builder(flatten)]
+    common: CommonOpts,
+}
+
+fn main() {}
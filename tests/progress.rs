@@ -10,4 +10,49 @@ fn tests() {
     t.pass("tests/07-repeated-field.rs");
     t.compile_fail("tests/08-unrecognized-attribute.rs");
     t.pass("tests/09-redefined-prelude-types.rs");
+    t.pass("tests/10-merge.rs");
+    t.compile_fail("tests/11-merge-without-each.rs");
+    t.pass("tests/12-env.rs");
+    t.pass("tests/13-set-by-name.rs");
+    t.pass("tests/14-cli.rs");
+    t.pass("tests/15-field-info.rs");
+    t.pass("tests/16-getters.rs");
+    t.compile_fail("tests/17-private-getter.rs");
+    t.pass("tests/18-rename.rs");
+    t.pass("tests/19-docs.rs");
+    t.compile_fail("tests/20-deprecated-setter.rs");
+    t.pass("tests/21-cfg-field.rs");
+    t.compile_fail("tests/22-cfg-field-disabled.rs");
+    t.pass("tests/23-sub-builder.rs");
+    t.compile_fail("tests/24-sub-builder-option.rs");
+    t.pass("tests/25-flatten.rs");
+    t.compile_fail("tests/26-flatten-name-clash.rs");
+    t.pass("tests/27-function.rs");
+    t.compile_fail("tests/28-function-self.rs");
+    t.pass("tests/29-start-fn.rs");
+    t.compile_fail("tests/30-start-fn-each.rs");
+    t.pass("tests/31-groups.rs");
+    t.compile_fail("tests/32-unknown-group.rs");
+    t.pass("tests/33-wrap.rs");
+    t.compile_fail("tests/34-wrap-plain-type.rs");
+    t.pass("tests/35-debug.rs");
+    t.pass("tests/36-optional-each.rs");
+    t.pass("tests/37-cardinality.rs");
+    t.compile_fail("tests/38-min-without-each.rs");
+    t.pass("tests/39-computed.rs");
+    t.compile_fail("tests/40-computed-each.rs");
+    t.pass("tests/41-alias-flag.rs");
+    t.compile_fail("tests/42-alias-collision.rs");
+    t.compile_fail("tests/43-method-collision.rs");
+    t.pass("tests/44-set-once.rs");
+    t.compile_fail("tests/45-set-once-each.rs");
+    t.pass("tests/46-custom-field.rs");
+    t.compile_fail("tests/47-custom-field-each.rs");
+    t.pass("tests/48-traits.rs");
+    t.pass("tests/49-build-macro.rs");
+    t.compile_fail("tests/50-build-macro-duplicate.rs");
+    t.pass("tests/51-flatten-use.rs");
+    t.compile_fail("tests/52-function-arguments.rs");
+    t.compile_fail("tests/53-flatten-not-flattenable.rs");
+    t.compile_fail("tests/54-flatten-without-cli.rs");
 }